@@ -0,0 +1,190 @@
+use crate::encode::EncodeOptions;
+use crate::error::AppError;
+use crate::ffmpeg::{
+    check_job_id, file_fingerprint, get_video_duration, is_split_running, CutMode, OutputOptions,
+    TimeRange,
+};
+use crate::queue::is_job_pending;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// How far a finished output's duration may be off its range and still count as complete.
+const DURATION_TOLERANCE_SECONDS: f64 = 1.0;
+
+/// Serializes updates to checkpoint files from parallel range workers.
+static CHECKPOINT_LOCK: Mutex<()> = Mutex::new(());
+
+/// On-disk state of a range split, rewritten as each segment completes.
+/// The file is removed when the split returns, so one that is still there
+/// on startup belongs to a run that was interrupted.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobCheckpoint {
+    pub job_id: String,
+    pub input_path: String,
+    pub fingerprint: Option<String>,
+    pub output_dir: String,
+    pub ranges: Vec<TimeRange>,
+    pub mode: CutMode,
+    #[serde(default)]
+    pub encode: EncodeOptions,
+    #[serde(flatten)]
+    pub output: OutputOptions,
+    /// Output path for every range, in range order. `None` marks a range that
+    /// was skipped because its file already existed.
+    pub output_files: Vec<Option<String>>,
+    /// Length each output should have, after keyframe snapping.
+    pub segment_durations: Vec<f64>,
+    /// Indices of ranges whose output was written completely.
+    pub completed: Vec<usize>,
+}
+
+/// An interrupted split found on startup, with its outputs checked via ffprobe.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnfinishedJob {
+    pub checkpoint: JobCheckpoint,
+    /// Index of the first range without a valid output; resuming starts here.
+    pub resume_from: usize,
+    /// The input no longer matches the fingerprint taken when the job started.
+    pub input_changed: bool,
+}
+
+fn checkpoint_dir(app_handle: &AppHandle) -> Result<PathBuf, AppError> {
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::internal(format!("Failed to resolve app data dir: {}", e)))?
+        .join("unfinished-jobs");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| AppError::io("Failed to create checkpoint dir", e))?;
+    Ok(dir)
+}
+
+fn checkpoint_path(app_handle: &AppHandle, job_id: &str) -> Result<PathBuf, AppError> {
+    check_job_id(job_id)?;
+    Ok(checkpoint_dir(app_handle)?.join(format!("{job_id}.json")))
+}
+
+fn write_checkpoint(path: &PathBuf, checkpoint: &JobCheckpoint) -> Result<(), AppError> {
+    let json = serde_json::to_string_pretty(checkpoint)
+        .map_err(|e| AppError::internal(format!("Failed to serialize checkpoint: {}", e)))?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json).map_err(|e| AppError::io("Failed to write checkpoint", e))?;
+    std::fs::rename(&tmp, path).map_err(|e| AppError::io("Failed to write checkpoint", e))
+}
+
+fn read_checkpoint(path: &PathBuf) -> Option<JobCheckpoint> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+/// Start tracking a range split. `completed` may already list ranges kept from
+/// an earlier run.
+pub fn begin(app_handle: &AppHandle, checkpoint: &JobCheckpoint) {
+    let Ok(path) = checkpoint_path(app_handle, &checkpoint.job_id) else {
+        return;
+    };
+    if let Ok(_lock) = CHECKPOINT_LOCK.lock() {
+        let _ = write_checkpoint(&path, checkpoint);
+    }
+}
+
+pub fn mark_segment_done(app_handle: &AppHandle, job_id: &str, index: usize) {
+    let Ok(path) = checkpoint_path(app_handle, job_id) else {
+        return;
+    };
+    let Ok(_lock) = CHECKPOINT_LOCK.lock() else {
+        return;
+    };
+    if let Some(mut checkpoint) = read_checkpoint(&path) {
+        if !checkpoint.completed.contains(&index) {
+            checkpoint.completed.push(index);
+            checkpoint.completed.sort_unstable();
+        }
+        let _ = write_checkpoint(&path, &checkpoint);
+    }
+}
+
+/// Forget a split that returned, whether it succeeded, failed or was cancelled.
+pub fn finish(app_handle: &AppHandle, job_id: &str) {
+    if let Ok(path) = checkpoint_path(app_handle, job_id) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+async fn output_is_valid(app_handle: &AppHandle, output_file: &str, expected: f64) -> bool {
+    if !std::path::Path::new(output_file).exists() {
+        return false;
+    }
+    match get_video_duration(app_handle, output_file).await {
+        Ok(duration) => {
+            duration > 0.0
+                && (duration - expected).abs() <= DURATION_TOLERANCE_SECONDS.max(expected * 0.05)
+        }
+        Err(_) => false,
+    }
+}
+
+/// A checkpoint whose split is still queued or running in this session is not
+/// interrupted, just unfinished.
+pub fn is_in_progress(app_handle: &AppHandle, job_id: &str) -> bool {
+    is_split_running(job_id) || is_job_pending(app_handle, job_id)
+}
+
+/// Checkpoints left behind by interrupted runs, each with the first range to redo.
+pub async fn list_unfinished(app_handle: &AppHandle) -> Result<Vec<UnfinishedJob>, AppError> {
+    let dir = checkpoint_dir(app_handle)?;
+    let paths = std::fs::read_dir(&dir)
+        .map_err(|e| AppError::io("Failed to read checkpoint dir", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+
+    let mut jobs = Vec::new();
+    for path in paths {
+        let Some(checkpoint) = read_checkpoint(&path) else {
+            continue;
+        };
+        if is_in_progress(app_handle, &checkpoint.job_id) {
+            continue;
+        }
+
+        let mut resume_from = checkpoint.ranges.len();
+        for (i, (output_file, expected)) in checkpoint
+            .output_files
+            .iter()
+            .zip(&checkpoint.segment_durations)
+            .enumerate()
+        {
+            let done = match output_file {
+                Some(output_file) => {
+                    checkpoint.completed.contains(&i)
+                        && output_is_valid(app_handle, output_file, *expected).await
+                }
+                None => true,
+            };
+            if !done {
+                resume_from = i;
+                break;
+            }
+        }
+
+        let input_changed = file_fingerprint(&checkpoint.input_path) != checkpoint.fingerprint;
+        jobs.push(UnfinishedJob {
+            checkpoint,
+            resume_from,
+            input_changed,
+        });
+    }
+    Ok(jobs)
+}
+
+/// Load a checkpoint without validating its outputs.
+pub fn load(app_handle: &AppHandle, job_id: &str) -> Result<JobCheckpoint, AppError> {
+    let path = checkpoint_path(app_handle, job_id)?;
+    read_checkpoint(&path)
+        .ok_or_else(|| AppError::not_found(format!("Unfinished job {} not found", job_id)))
+}
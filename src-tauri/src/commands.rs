@@ -1,4 +1,11 @@
-use crate::ffmpeg::{check_ffmpeg, format_duration, get_video_duration, prepare_hls_source, PreviewSource, split_video, split_video_by_ranges, FFmpegStatus, SplitResult, TimeRange, VideoInfo};
+use crate::ffmpeg::{cancel_split, check_ffmpeg, CutMode, detect_scenes, detect_silence, extract_frame, ExtractedFrame, format_duration, ImageFormat, generate_filmstrip, get_chapters, Filmstrip, get_keyframes_cached, get_video_duration, get_waveform, new_job_id, OutputOptions, predict_segment_boundaries, probe_media, prepare_hls_source, PreviewSource, FFmpegStatus, SplitResult, ThumbnailMode, TimeRange, VideoInfo, Waveform};
+use crate::checkpoint::{self, JobCheckpoint, UnfinishedJob};
+use crate::encode::{self, Container, EncodeOptions, EncoderSupport};
+use crate::error::AppError;
+use crate::history::{self, HistoryEntry};
+use crate::naming::OverwritePolicy;
+use crate::presets::{self, EncodePreset};
+use crate::queue::{self, run_split, QueuedJob, RangeResume, SplitSpec};
 use tauri::{AppHandle, Manager};
 
 #[tauri::command]
@@ -7,8 +14,9 @@ pub async fn check_ffmpeg_command(app_handle: AppHandle) -> FFmpegStatus {
 }
 
 #[tauri::command]
-pub async fn get_video_info(app_handle: AppHandle, path: String) -> Result<VideoInfo, String> {
-    let duration = get_video_duration(&app_handle, &path).await?;
+pub async fn get_video_info(app_handle: AppHandle, path: String) -> Result<VideoInfo, AppError> {
+    let media = probe_media(&app_handle, &path).await?;
+    let duration = media.duration;
     let duration_formatted = format_duration(duration);
 
     let file_path = std::path::Path::new(&path);
@@ -22,33 +30,455 @@ pub async fn get_video_info(app_handle: AppHandle, path: String) -> Result<Video
         .map(|m| m.len())
         .unwrap_or(0);
 
+    // Chapters are optional metadata; a probe failure should not block loading the file.
+    let chapters = get_chapters(&app_handle, &path).await.unwrap_or_default();
+
     Ok(VideoInfo {
         path,
         duration,
         duration_formatted,
         filename,
         file_size,
+        chapters,
+        format_name: media.format_name,
+        bit_rate: media.bit_rate,
+        video: media.video,
+        audio_tracks: media.audio_tracks,
+        subtitle_tracks: media.subtitle_tracks,
+        tags: media.tags,
     })
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn split_video_command(
     app_handle: AppHandle,
     input_path: String,
     output_dir: String,
     segment_duration: u32,
-) -> Result<SplitResult, String> {
-    split_video(&app_handle, &input_path, &output_dir, segment_duration).await
+    thumbnails: Option<ThumbnailMode>,
+    container: Option<Container>,
+    name_template: Option<String>,
+    overwrite: Option<OverwritePolicy>,
+    job_id: Option<String>,
+) -> Result<SplitResult, AppError> {
+    let job_id = job_id.unwrap_or_else(new_job_id);
+    let spec = SplitSpec::Duration {
+        segment_duration,
+        output: OutputOptions {
+            container,
+            name_template,
+            overwrite: overwrite.unwrap_or_default(),
+        },
+    };
+    run_split(&app_handle, &job_id, &input_path, &output_dir, &spec, thumbnails, None).await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn split_video_by_chapters_command(
+    app_handle: AppHandle,
+    input_path: String,
+    output_dir: String,
+    mode: Option<CutMode>,
+    encode: Option<EncodeOptions>,
+    container: Option<Container>,
+    name_template: Option<String>,
+    overwrite: Option<OverwritePolicy>,
+    job_id: Option<String>,
+) -> Result<SplitResult, AppError> {
+    let job_id = job_id.unwrap_or_else(new_job_id);
+    let spec = SplitSpec::Chapters {
+        mode: mode.unwrap_or_default(),
+        encode: encode.unwrap_or_default(),
+        output: OutputOptions {
+            container,
+            name_template,
+            overwrite: overwrite.unwrap_or_default(),
+        },
+    };
+    run_split(&app_handle, &job_id, &input_path, &output_dir, &spec, None, None).await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn split_video_by_count_command(
+    app_handle: AppHandle,
+    input_path: String,
+    output_dir: String,
+    part_count: u32,
+    container: Option<Container>,
+    name_template: Option<String>,
+    overwrite: Option<OverwritePolicy>,
+    job_id: Option<String>,
+) -> Result<SplitResult, AppError> {
+    let job_id = job_id.unwrap_or_else(new_job_id);
+    let spec = SplitSpec::Count {
+        part_count,
+        output: OutputOptions {
+            container,
+            name_template,
+            overwrite: overwrite.unwrap_or_default(),
+        },
+    };
+    run_split(&app_handle, &job_id, &input_path, &output_dir, &spec, None, None).await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn split_video_by_size_command(
+    app_handle: AppHandle,
+    input_path: String,
+    output_dir: String,
+    max_bytes: u64,
+    container: Option<Container>,
+    name_template: Option<String>,
+    overwrite: Option<OverwritePolicy>,
+    job_id: Option<String>,
+) -> Result<SplitResult, AppError> {
+    let job_id = job_id.unwrap_or_else(new_job_id);
+    let spec = SplitSpec::Size {
+        max_bytes,
+        output: OutputOptions {
+            container,
+            name_template,
+            overwrite: overwrite.unwrap_or_default(),
+        },
+    };
+    run_split(&app_handle, &job_id, &input_path, &output_dir, &spec, None, None).await
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn split_video_by_ranges_command(
     app_handle: AppHandle,
     input_path: String,
     output_dir: String,
     ranges: Vec<TimeRange>,
-) -> Result<SplitResult, String> {
-    split_video_by_ranges(&app_handle, &input_path, &output_dir, ranges).await
+    mode: Option<CutMode>,
+    thumbnails: Option<ThumbnailMode>,
+    max_workers: Option<usize>,
+    encode: Option<EncodeOptions>,
+    preset: Option<String>,
+    container: Option<Container>,
+    name_template: Option<String>,
+    overwrite: Option<OverwritePolicy>,
+    job_id: Option<String>,
+) -> Result<SplitResult, AppError> {
+    let job_id = job_id.unwrap_or_else(new_job_id);
+    // The resolved options go into the spec, so history reruns are unaffected
+    // by later edits to the preset.
+    let encode = match (encode, preset) {
+        (Some(_), Some(_)) => {
+            return Err(AppError::invalid("Pass either encode options or a preset, not both"));
+        }
+        (_, Some(name)) => presets::get_preset(&app_handle, &name)?.encode,
+        (encode, None) => encode.unwrap_or_default(),
+    };
+    let spec = SplitSpec::Ranges {
+        ranges,
+        mode: mode.unwrap_or_default(),
+        max_workers,
+        encode,
+        output: OutputOptions {
+            container,
+            name_template,
+            overwrite: overwrite.unwrap_or_default(),
+        },
+    };
+    run_split(&app_handle, &job_id, &input_path, &output_dir, &spec, thumbnails, None).await
+}
+
+/// Video and audio codecs the bundled ffmpeg can encode, for the export settings.
+#[tauri::command]
+pub async fn get_encoder_support(app_handle: AppHandle) -> Result<EncoderSupport, AppError> {
+    encode::encoder_support(&app_handle).await
+}
+
+#[tauri::command]
+pub async fn list_presets(app_handle: AppHandle) -> Result<Vec<EncodePreset>, AppError> {
+    presets::list_presets(&app_handle)
+}
+
+/// Save the options under `name`, replacing a user preset with that name.
+#[tauri::command]
+pub async fn save_preset(
+    app_handle: AppHandle,
+    name: String,
+    encode: EncodeOptions,
+) -> Result<EncodePreset, AppError> {
+    presets::save_preset(&app_handle, &name, encode).await
+}
+
+#[tauri::command]
+pub async fn delete_preset(app_handle: AppHandle, name: String) -> Result<(), AppError> {
+    presets::delete_preset(&app_handle, &name)
+}
+
+/// Range splits that were interrupted by a crash or quit, with their outputs
+/// checked via ffprobe.
+#[tauri::command]
+pub async fn list_unfinished_jobs(
+    app_handle: AppHandle,
+) -> Result<Vec<UnfinishedJob>, AppError> {
+    checkpoint::list_unfinished(&app_handle).await
+}
+
+/// Queue an interrupted range split again, starting at its first missing segment.
+/// A job whose input changed since it started is only resumed with `confirm_input_changed`,
+/// since its earlier outputs were cut from the old file.
+#[tauri::command]
+pub async fn resume_unfinished_job(
+    app_handle: AppHandle,
+    job_id: String,
+    confirm_input_changed: Option<bool>,
+) -> Result<QueuedJob, AppError> {
+    if checkpoint::is_in_progress(&app_handle, &job_id) {
+        return Err(AppError::invalid(format!("Split job {} is still running", job_id)));
+    }
+    let unfinished = checkpoint::list_unfinished(&app_handle)
+        .await?
+        .into_iter()
+        .find(|job| job.checkpoint.job_id == job_id)
+        .ok_or_else(|| AppError::not_found(format!("Unfinished job {} not found", job_id)))?;
+    if unfinished.input_changed && !confirm_input_changed.unwrap_or(false) {
+        return Err(AppError::invalid(format!(
+            "The input of job {} changed since it was interrupted",
+            job_id
+        )));
+    }
+    let JobCheckpoint {
+        input_path,
+        output_dir,
+        ranges,
+        mode,
+        encode,
+        output,
+        output_files,
+        ..
+    } = unfinished.checkpoint;
+
+    let queued = queue::enqueue_job(
+        &app_handle,
+        input_path,
+        output_dir,
+        SplitSpec::Ranges {
+            ranges,
+            mode,
+            max_workers: None,
+            encode,
+            output,
+        },
+        None,
+        Some(RangeResume {
+            resume_from: unfinished.resume_from,
+            planned_outputs: output_files,
+        }),
+    )?;
+    checkpoint::finish(&app_handle, &job_id);
+    Ok(queued)
+}
+
+#[tauri::command]
+pub async fn discard_unfinished_job(
+    app_handle: AppHandle,
+    job_id: String,
+) -> Result<(), AppError> {
+    if checkpoint::is_in_progress(&app_handle, &job_id) {
+        return Err(AppError::invalid(format!("Split job {} is still running", job_id)));
+    }
+    checkpoint::load(&app_handle, &job_id)?;
+    checkpoint::finish(&app_handle, &job_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_history(app_handle: AppHandle) -> Result<Vec<HistoryEntry>, AppError> {
+    history::list_history(&app_handle)
+}
+
+/// Queue a past split again with the same parameters. `input_path` and
+/// `output_dir` override the recorded ones, e.g. for a re-exported master.
+#[tauri::command]
+pub async fn rerun_history_entry(
+    app_handle: AppHandle,
+    entry_id: String,
+    input_path: Option<String>,
+    output_dir: Option<String>,
+) -> Result<QueuedJob, AppError> {
+    let entry = history::get_history_entry(&app_handle, &entry_id)?;
+    queue::enqueue_job(
+        &app_handle,
+        input_path.unwrap_or(entry.input_path),
+        output_dir.unwrap_or(entry.output_dir),
+        entry.spec,
+        None,
+        None,
+    )
+}
+
+/// Add a split job to the background queue. Progress events use the returned job id.
+#[tauri::command]
+pub async fn enqueue_job(
+    app_handle: AppHandle,
+    input_path: String,
+    output_dir: String,
+    spec: SplitSpec,
+    thumbnails: Option<ThumbnailMode>,
+) -> Result<QueuedJob, AppError> {
+    queue::enqueue_job(&app_handle, input_path, output_dir, spec, thumbnails, None)
+}
+
+#[tauri::command]
+pub async fn list_jobs(app_handle: AppHandle) -> Result<Vec<QueuedJob>, AppError> {
+    queue::list_jobs(&app_handle)
+}
+
+/// Remove a job from the queue, cancelling it if it is running.
+#[tauri::command]
+pub async fn remove_job(app_handle: AppHandle, job_id: String) -> Result<bool, AppError> {
+    queue::remove_job(&app_handle, &job_id)
+}
+
+#[tauri::command]
+pub async fn reorder_jobs(
+    app_handle: AppHandle,
+    job_ids: Vec<String>,
+) -> Result<Vec<QueuedJob>, AppError> {
+    queue::reorder_jobs(&app_handle, &job_ids)
+}
+
+#[tauri::command]
+pub async fn set_queue_concurrency(
+    app_handle: AppHandle,
+    max_concurrent: usize,
+) -> Result<(), AppError> {
+    queue::set_max_concurrent(&app_handle, max_concurrent)
+}
+
+/// Cancel a running split job. Returns `false` if the job already finished.
+#[tauri::command]
+pub async fn cancel_split_command(job_id: String) -> Result<bool, AppError> {
+    cancel_split(&job_id)
+}
+
+/// Detect scene changes and return them as ranges for review in the range editor.
+#[tauri::command]
+pub async fn detect_scenes_command(
+    app_handle: AppHandle,
+    input_path: String,
+    threshold: f64,
+    min_segment_seconds: f64,
+    job_id: Option<String>,
+) -> Result<Vec<TimeRange>, AppError> {
+    let job_id = job_id.unwrap_or_else(new_job_id);
+    detect_scenes(
+        &app_handle,
+        &job_id,
+        &input_path,
+        threshold,
+        min_segment_seconds,
+    )
+    .await
+}
+
+/// Detect silences and return ranges that cut in the middle of each one.
+#[tauri::command]
+pub async fn detect_silence_command(
+    app_handle: AppHandle,
+    input_path: String,
+    noise_db: f64,
+    min_silence_seconds: f64,
+    job_id: Option<String>,
+) -> Result<Vec<TimeRange>, AppError> {
+    let job_id = job_id.unwrap_or_else(new_job_id);
+    detect_silence(
+        &app_handle,
+        &job_id,
+        &input_path,
+        noise_db,
+        min_silence_seconds,
+    )
+    .await
+}
+
+/// Sorted keyframe timestamps of the first video stream, cached per file.
+#[tauri::command]
+pub async fn get_keyframes_command(
+    app_handle: AppHandle,
+    input_path: String,
+) -> Result<Vec<f64>, AppError> {
+    get_keyframes_cached(&app_handle, &input_path).await
+}
+
+/// The parts `split_video_command` will produce, based on the keyframe index.
+#[tauri::command]
+pub async fn predict_segments_command(
+    app_handle: AppHandle,
+    input_path: String,
+    segment_duration: u32,
+) -> Result<Vec<TimeRange>, AppError> {
+    let duration = get_video_duration(&app_handle, &input_path).await?;
+    let keyframes = get_keyframes_cached(&app_handle, &input_path).await?;
+    Ok(predict_segment_boundaries(
+        &keyframes,
+        segment_duration as f64,
+        duration,
+    ))
+}
+
+/// Thumbnail sprite sheet for scrubbing. Pass `count` or `interval_seconds`.
+#[tauri::command]
+pub async fn generate_filmstrip_command(
+    app_handle: AppHandle,
+    input_path: String,
+    count: Option<u32>,
+    interval_seconds: Option<f64>,
+    width: Option<u32>,
+) -> Result<Filmstrip, AppError> {
+    generate_filmstrip(
+        &app_handle,
+        &input_path,
+        count,
+        interval_seconds,
+        width.unwrap_or(160),
+    )
+    .await
+}
+
+/// Min/max audio peaks for drawing a waveform under the range editor.
+/// Cancellable with `cancel_split_command` under `job_id`.
+#[tauri::command]
+pub async fn get_waveform_command(
+    app_handle: AppHandle,
+    input_path: String,
+    buckets: u32,
+    job_id: Option<String>,
+) -> Result<Waveform, AppError> {
+    let job_id = job_id.unwrap_or_else(new_job_id);
+    get_waveform(&app_handle, &job_id, &input_path, buckets).await
+}
+
+/// Save the exact frame at `time_seconds` as an image. Without `output_dir`
+/// the image goes to the temp cache.
+#[tauri::command]
+pub async fn extract_frame_command(
+    app_handle: AppHandle,
+    input_path: String,
+    time_seconds: f64,
+    format: Option<ImageFormat>,
+    width: Option<u32>,
+    output_dir: Option<String>,
+) -> Result<ExtractedFrame, AppError> {
+    extract_frame(
+        &app_handle,
+        &input_path,
+        time_seconds,
+        format.unwrap_or_default(),
+        width,
+        output_dir.as_deref(),
+    )
+    .await
 }
 
 #[tauri::command]
@@ -59,7 +489,7 @@ pub async fn prepare_hls_source_command(
     segment_seconds: u64,
     start_seconds: Option<f64>,
     window_seconds: Option<u64>,
-) -> Result<PreviewSource, String> {
+) -> Result<PreviewSource, AppError> {
     prepare_hls_source(
         &app_handle,
         &input_path,
@@ -73,20 +503,20 @@ pub async fn prepare_hls_source_command(
 
 /// Allow a user-selected file or directory for the asset protocol.
 #[tauri::command]
-pub async fn allow_asset_path(app_handle: AppHandle, path: String, is_dir: bool) -> Result<(), String> {
+pub async fn allow_asset_path(app_handle: AppHandle, path: String, is_dir: bool) -> Result<(), AppError> {
     let scope = app_handle.asset_protocol_scope();
     if is_dir {
         scope
             .allow_directory(path, true)
-            .map_err(|e| e.to_string())
+            .map_err(AppError::internal)
     } else {
-        scope.allow_file(path).map_err(|e| e.to_string())
+        scope.allow_file(path).map_err(AppError::internal)
     }
 }
 
 /// Select output directory (uses native dialog)
 #[tauri::command]
-pub async fn select_directory() -> Result<Option<String>, String> {
+pub async fn select_directory() -> Result<Option<String>, AppError> {
     // This will be handled by tauri-plugin-dialog on the frontend
     Ok(None)
 }
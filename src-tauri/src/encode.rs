@@ -0,0 +1,774 @@
+use crate::error::AppError;
+use crate::ffmpeg::probe_media;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::OnceLock;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+/// Encoders compiled into the bundled ffmpeg. The sidecar never changes while
+/// the app runs, so `ffmpeg -encoders` is only parsed once.
+static AVAILABLE_ENCODERS: OnceLock<HashSet<String>> = OnceLock::new();
+
+const X26X_PRESETS: &[&str] = &[
+    "ultrafast",
+    "superfast",
+    "veryfast",
+    "faster",
+    "fast",
+    "medium",
+    "slow",
+    "slower",
+    "veryslow",
+    "placebo",
+];
+
+const PRORES_PROFILES: &[&str] = &["proxy", "lt", "standard", "hq", "4444", "4444xq"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum VideoCodec {
+    #[default]
+    X264,
+    X265,
+    Vp9,
+    /// AV1 via SVT-AV1.
+    Av1,
+    Prores,
+}
+
+impl VideoCodec {
+    pub fn encoder(self) -> &'static str {
+        match self {
+            VideoCodec::X264 => "libx264",
+            VideoCodec::X265 => "libx265",
+            VideoCodec::Vp9 => "libvpx-vp9",
+            VideoCodec::Av1 => "libsvtav1",
+            VideoCodec::Prores => "prores_ks",
+        }
+    }
+
+    /// The codec name ffprobe reports for this encoder's output.
+    pub fn codec_name(self) -> &'static str {
+        match self {
+            VideoCodec::X264 => "h264",
+            VideoCodec::X265 => "hevc",
+            VideoCodec::Vp9 => "vp9",
+            VideoCodec::Av1 => "av1",
+            VideoCodec::Prores => "prores",
+        }
+    }
+
+    fn crf_range(self) -> Option<(u32, u32)> {
+        match self {
+            VideoCodec::X264 | VideoCodec::X265 => Some((0, 51)),
+            VideoCodec::Vp9 | VideoCodec::Av1 => Some((0, 63)),
+            VideoCodec::Prores => None,
+        }
+    }
+
+    fn default_crf(self) -> u32 {
+        match self {
+            VideoCodec::X264 => 18,
+            VideoCodec::X265 => 20,
+            VideoCodec::Vp9 => 31,
+            VideoCodec::Av1 => 30,
+            VideoCodec::Prores => 0,
+        }
+    }
+
+    /// `-preset` for x264/x265/SVT-AV1, `-cpu-used` for VP9 and `-profile:v` for ProRes.
+    fn default_preset(self) -> &'static str {
+        match self {
+            VideoCodec::X264 | VideoCodec::X265 => "veryfast",
+            VideoCodec::Vp9 => "4",
+            VideoCodec::Av1 => "8",
+            VideoCodec::Prores => "hq",
+        }
+    }
+
+    fn accepts_preset(self, preset: &str) -> bool {
+        let numeric = |max: i32| preset.parse::<i32>().is_ok_and(|n| (0..=max).contains(&n));
+        match self {
+            VideoCodec::X264 | VideoCodec::X265 => X26X_PRESETS.contains(&preset),
+            VideoCodec::Vp9 => numeric(8),
+            VideoCodec::Av1 => numeric(13),
+            VideoCodec::Prores => PRORES_PROFILES.contains(&preset) || numeric(5),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioCodec {
+    #[default]
+    Aac,
+    Opus,
+    Mp3,
+    Flac,
+    /// Uncompressed 16-bit PCM.
+    Pcm,
+    /// Keep the source audio as-is.
+    Copy,
+}
+
+impl AudioCodec {
+    pub fn encoder(self) -> &'static str {
+        match self {
+            AudioCodec::Aac => "aac",
+            AudioCodec::Opus => "libopus",
+            AudioCodec::Mp3 => "libmp3lame",
+            AudioCodec::Flac => "flac",
+            AudioCodec::Pcm => "pcm_s16le",
+            AudioCodec::Copy => "copy",
+        }
+    }
+
+    /// The codec name ffprobe reports for this encoder's output.
+    fn codec_name(self) -> Option<&'static str> {
+        match self {
+            AudioCodec::Aac => Some("aac"),
+            AudioCodec::Opus => Some("opus"),
+            AudioCodec::Mp3 => Some("mp3"),
+            AudioCodec::Flac => Some("flac"),
+            AudioCodec::Pcm => Some("pcm_s16le"),
+            AudioCodec::Copy => None,
+        }
+    }
+
+    fn is_lossy(self) -> bool {
+        matches!(self, AudioCodec::Aac | AudioCodec::Opus | AudioCodec::Mp3)
+    }
+}
+
+/// Encoder settings for re-encoded range exports. The default reproduces the
+/// previous fixed `libx264 -preset veryfast -crf 18` with AAC audio.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct EncodeOptions {
+    pub video_codec: VideoCodec,
+    /// Constant-quality factor. Ignored when `video_bitrate` is set.
+    pub crf: Option<u32>,
+    /// Target video bitrate in ffmpeg notation, e.g. `8M` or `2500k`.
+    pub video_bitrate: Option<String>,
+    /// Speed preset; for ProRes this selects the profile (`proxy` .. `4444xq`).
+    pub preset: Option<String>,
+    pub pix_fmt: Option<String>,
+    /// Downscale to at most this many lines, keeping the aspect ratio.
+    pub max_height: Option<u32>,
+    pub audio_codec: AudioCodec,
+    /// Audio bitrate for lossy codecs, e.g. `192k`.
+    pub audio_bitrate: Option<String>,
+}
+
+impl EncodeOptions {
+    /// `-c:v`/`-c:a`, rate-control and scaling arguments for ffmpeg, for an
+    /// output with the given file extension.
+    pub fn codec_args(&self, extension: &str) -> Vec<String> {
+        let mut args = self.video_args(extension);
+        args.extend(self.audio_args("a"));
+        args
+    }
+
+    fn video_args(&self, extension: &str) -> Vec<String> {
+        let codec = self.video_codec;
+        let preset = self
+            .preset
+            .clone()
+            .unwrap_or_else(|| codec.default_preset().to_string());
+        let mut args = vec!["-c:v".to_string(), codec.encoder().to_string()];
+
+        match codec {
+            VideoCodec::X264 | VideoCodec::X265 | VideoCodec::Av1 => {
+                args.extend(["-preset".to_string(), preset]);
+            }
+            VideoCodec::Vp9 => {
+                args.extend([
+                    "-deadline".to_string(),
+                    "good".to_string(),
+                    "-cpu-used".to_string(),
+                    preset,
+                    "-row-mt".to_string(),
+                    "1".to_string(),
+                ]);
+            }
+            VideoCodec::Prores => {
+                args.extend(["-profile:v".to_string(), preset]);
+            }
+        }
+
+        if codec != VideoCodec::Prores {
+            match &self.video_bitrate {
+                Some(bitrate) => args.extend(["-b:v".to_string(), bitrate.clone()]),
+                None => {
+                    let crf = self.crf.unwrap_or_else(|| codec.default_crf());
+                    args.extend(["-crf".to_string(), crf.to_string()]);
+                    // libvpx only treats -crf as constant quality with no bitrate cap.
+                    if codec == VideoCodec::Vp9 {
+                        args.extend(["-b:v".to_string(), "0".to_string()]);
+                    }
+                }
+            }
+        }
+
+        let pix_fmt = match (&self.pix_fmt, codec) {
+            (Some(pix_fmt), _) => Some(pix_fmt.as_str()),
+            (None, VideoCodec::Prores) => Some("yuv422p10le"),
+            (None, _) => None,
+        };
+        if let Some(pix_fmt) = pix_fmt {
+            args.extend(["-pix_fmt".to_string(), pix_fmt.to_string()]);
+        }
+        // Never upscale smaller sources; -2 keeps the width even for 4:2:0.
+        if let Some(height) = self.max_height {
+            args.extend(["-vf".to_string(), format!("scale=-2:'min({},ih)'", height)]);
+        }
+        // Apple players only recognise HEVC in MP4/MOV with the hvc1 tag.
+        let isobmff = matches!(extension.to_lowercase().as_str(), "mp4" | "m4v" | "mov");
+        if codec == VideoCodec::X265 && isobmff {
+            args.extend(["-tag:v".to_string(), "hvc1".to_string()]);
+        }
+        args
+    }
+
+    /// Audio codec arguments for the output streams matched by `specifier`,
+    /// e.g. `a` for all audio or `a:1` for the second track.
+    fn audio_args(&self, specifier: &str) -> Vec<String> {
+        let mut args = vec![
+            format!("-c:{}", specifier),
+            self.audio_codec.encoder().to_string(),
+        ];
+        if let (Some(bitrate), true) = (&self.audio_bitrate, self.audio_codec.is_lossy()) {
+            args.extend([format!("-b:{}", specifier), bitrate.clone()]);
+        }
+        args
+    }
+
+    /// Check values that ffmpeg would otherwise only reject mid-export.
+    fn check_values(&self) -> Result<(), AppError> {
+        let codec = self.video_codec;
+        if let Some(crf) = self.crf {
+            match codec.crf_range() {
+                Some((min, max)) if (min..=max).contains(&crf) => {}
+                Some((min, max)) => {
+                    return Err(AppError::invalid(format!(
+                        "CRF for {} must be between {} and {}",
+                        codec.encoder(),
+                        min,
+                        max
+                    )));
+                }
+                None => {
+                    return Err(AppError::invalid(
+                        "ProRes has no CRF; choose a profile via the preset instead",
+                    ));
+                }
+            }
+        }
+        if let Some(bitrate) = &self.video_bitrate {
+            if codec == VideoCodec::Prores {
+                return Err(AppError::invalid(
+                    "ProRes bitrate is fixed by its profile; choose one via the preset instead",
+                ));
+            }
+            if !is_bitrate(bitrate) {
+                return Err(AppError::invalid(format!("Invalid video bitrate: {}", bitrate)));
+            }
+        }
+        if let Some(preset) = &self.preset {
+            if !codec.accepts_preset(preset) {
+                return Err(AppError::invalid(format!(
+                    "Unknown preset {} for {}",
+                    preset,
+                    codec.encoder()
+                )));
+            }
+        }
+        if let Some(pix_fmt) = &self.pix_fmt {
+            let valid = !pix_fmt.is_empty()
+                && pix_fmt.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(AppError::invalid(format!("Invalid pixel format: {}", pix_fmt)));
+            }
+        }
+        if let Some(height) = self.max_height {
+            if !(16..=8640).contains(&height) || height % 2 != 0 {
+                return Err(AppError::invalid(format!(
+                    "Maximum height must be an even number between 16 and 8640, got {}",
+                    height
+                )));
+            }
+        }
+        if let Some(bitrate) = &self.audio_bitrate {
+            if !is_bitrate(bitrate) {
+                return Err(AppError::invalid(format!("Invalid audio bitrate: {}", bitrate)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `2500k`, `8M`, `1.5M` or a plain number of bits per second.
+fn is_bitrate(value: &str) -> bool {
+    let number = value.trim_end_matches(['k', 'K', 'm', 'M']);
+    value.len() - number.len() <= 1 && number.parse::<f64>().is_ok_and(|n| n > 0.0)
+}
+
+/// Names of the encoders the bundled ffmpeg was built with.
+pub async fn available_encoders(
+    app_handle: &AppHandle,
+) -> Result<&'static HashSet<String>, AppError> {
+    if let Some(encoders) = AVAILABLE_ENCODERS.get() {
+        return Ok(encoders);
+    }
+
+    let output = app_handle
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| AppError::sidecar("ffmpeg", e))?
+        .args(["-hide_banner", "-encoders"])
+        .output()
+        .await
+        .map_err(|e| AppError::sidecar("ffmpeg", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::ffmpeg("Failed to list encoders", &stderr));
+    }
+
+    // Entries follow a legend terminated by a " ------" line:
+    // " V....D libx264              libx264 H.264 / AVC / MPEG-4 AVC ..."
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let encoders = stdout
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("---"))
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(str::to_string)
+        .collect::<HashSet<_>>();
+    Ok(AVAILABLE_ENCODERS.get_or_init(|| encoders))
+}
+
+/// Which of the selectable codecs the bundled ffmpeg can encode.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EncoderSupport {
+    pub video: Vec<VideoCodec>,
+    pub audio: Vec<AudioCodec>,
+}
+
+pub async fn encoder_support(app_handle: &AppHandle) -> Result<EncoderSupport, AppError> {
+    let encoders = available_encoders(app_handle).await?;
+    let video = [
+        VideoCodec::X264,
+        VideoCodec::X265,
+        VideoCodec::Vp9,
+        VideoCodec::Av1,
+        VideoCodec::Prores,
+    ];
+    let audio = [
+        AudioCodec::Aac,
+        AudioCodec::Opus,
+        AudioCodec::Mp3,
+        AudioCodec::Flac,
+        AudioCodec::Pcm,
+    ];
+    Ok(EncoderSupport {
+        video: video
+            .into_iter()
+            .filter(|codec| encoders.contains(codec.encoder()))
+            .collect(),
+        audio: audio
+            .into_iter()
+            .filter(|codec| encoders.contains(codec.encoder()))
+            .chain([AudioCodec::Copy])
+            .collect(),
+    })
+}
+
+/// Reject options with out-of-range values or an encoder the bundled ffmpeg lacks.
+pub async fn validate_encode_options(
+    app_handle: &AppHandle,
+    options: &EncodeOptions,
+) -> Result<(), AppError> {
+    options.check_values()?;
+
+    let encoders = available_encoders(app_handle).await?;
+    let required = [options.video_codec.encoder(), options.audio_codec.encoder()];
+    for encoder in required {
+        if encoder != "copy" && !encoders.contains(encoder) {
+            return Err(AppError::UnsupportedCodec {
+                details: format!("The bundled FFmpeg has no {} encoder", encoder),
+                stderr_tail: None,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Output container for split parts. Where a command takes an
+/// `Option<Container>`, `None` keeps the input's own container.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Container {
+    Mp4,
+    Mkv,
+    Mov,
+    Webm,
+    Ts,
+}
+
+impl Container {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Container::Mp4 => "mp4",
+            Container::Mkv => "mkv",
+            Container::Mov => "mov",
+            Container::Webm => "webm",
+            Container::Ts => "ts",
+        }
+    }
+
+    /// The container an output with this file extension is written as, when
+    /// it is one of the selectable ones.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "mp4" | "m4v" => Some(Container::Mp4),
+            "mkv" => Some(Container::Mkv),
+            "mov" => Some(Container::Mov),
+            "webm" => Some(Container::Webm),
+            "ts" | "m2ts" | "mts" => Some(Container::Ts),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Container::Mp4 => "MP4",
+            Container::Mkv => "Matroska",
+            Container::Mov => "QuickTime",
+            Container::Webm => "WebM",
+            Container::Ts => "MPEG-TS",
+        }
+    }
+
+    /// Whether a stream with this ffprobe codec name can be stream-copied in.
+    fn accepts(self, kind: StreamKind, codec: &str) -> bool {
+        match (self, kind) {
+            (Container::Mkv, _) => true,
+            (Container::Mp4, StreamKind::Video) => matches!(
+                codec,
+                "h264" | "hevc" | "av1" | "vp9" | "mpeg4" | "mpeg2video" | "mpeg1video"
+            ),
+            (Container::Mp4, StreamKind::Audio) => matches!(
+                codec,
+                "aac" | "mp3" | "mp2" | "ac3" | "eac3" | "opus" | "flac" | "alac"
+            ),
+            (Container::Mov, StreamKind::Video) => matches!(
+                codec,
+                "h264" | "hevc" | "prores" | "mpeg4" | "mpeg2video" | "mjpeg" | "dnxhd"
+            ),
+            (Container::Mov, StreamKind::Audio) => {
+                matches!(codec, "aac" | "mp3" | "alac" | "ac3" | "eac3")
+                    || codec.starts_with("pcm_")
+            }
+            (Container::Mp4 | Container::Mov, StreamKind::Subtitle) => codec == "mov_text",
+            (Container::Webm, StreamKind::Video) => matches!(codec, "vp8" | "vp9" | "av1"),
+            (Container::Webm, StreamKind::Audio) => matches!(codec, "opus" | "vorbis"),
+            (Container::Webm, StreamKind::Subtitle) => codec == "webvtt",
+            (Container::Ts, StreamKind::Video) => matches!(
+                codec,
+                "h264" | "hevc" | "mpeg4" | "mpeg2video" | "mpeg1video"
+            ),
+            (Container::Ts, StreamKind::Audio) => matches!(
+                codec,
+                "aac" | "mp3" | "mp2" | "ac3" | "eac3" | "opus"
+            ),
+            (Container::Ts, StreamKind::Subtitle) => {
+                matches!(codec, "dvb_subtitle" | "dvb_teletext")
+            }
+        }
+    }
+
+    /// The text subtitle format the container stores, if any.
+    fn text_subtitle_codec(self) -> Option<&'static str> {
+        match self {
+            Container::Mp4 | Container::Mov => Some("mov_text"),
+            Container::Webm => Some("webvtt"),
+            Container::Mkv => Some("srt"),
+            Container::Ts => None,
+        }
+    }
+
+    fn default_video_codec(self) -> VideoCodec {
+        match self {
+            Container::Webm => VideoCodec::Vp9,
+            _ => VideoCodec::X264,
+        }
+    }
+
+    fn default_audio_codec(self) -> AudioCodec {
+        match self {
+            Container::Webm => AudioCodec::Opus,
+            _ => AudioCodec::Aac,
+        }
+    }
+
+    /// Reject re-encode settings whose output the container cannot hold.
+    pub fn check_encode(self, encode: &EncodeOptions) -> Result<(), AppError> {
+        let video = encode.video_codec;
+        let audio = encode.audio_codec.codec_name();
+        let rejected = if !self.accepts(StreamKind::Video, video.codec_name()) {
+            Some(video.encoder())
+        } else if audio.is_some_and(|codec| !self.accepts(StreamKind::Audio, codec)) {
+            Some(encode.audio_codec.encoder())
+        } else {
+            None
+        };
+        match rejected {
+            Some(encoder) => Err(AppError::invalid(format!(
+                "{} output cannot be stored in {}",
+                encoder,
+                self.name()
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Text-based subtitle codecs that ffmpeg can convert between containers.
+const TEXT_SUBTITLE_CODECS: &[&str] =
+    &["subrip", "srt", "ass", "ssa", "webvtt", "mov_text", "text"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamKind {
+    Video,
+    Audio,
+    Subtitle,
+}
+
+/// An input stream that could not be stream-copied into the requested container.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StreamConversion {
+    pub kind: StreamKind,
+    /// Input stream index; `None` for the main video stream.
+    pub stream_index: Option<u32>,
+    pub codec: String,
+    /// What the stream is transcoded to, or `None` when it is dropped.
+    pub target: Option<String>,
+    pub reason: String,
+}
+
+/// Which streams go into the output and how, once the requested container
+/// has been compared against the input's codecs.
+#[derive(Debug, Clone)]
+struct StreamPlan {
+    /// Encoder for the video when it cannot be copied.
+    video: Option<EncodeOptions>,
+    /// Input index and, when it cannot be copied, target codec of each audio track.
+    audio: Vec<(u32, Option<AudioCodec>)>,
+    /// Input index and, when it must be converted, target codec of each kept subtitle track.
+    subtitles: Vec<(u32, Option<&'static str>)>,
+}
+
+/// How split outputs are written: their extension and, when the container
+/// changes, which streams are copied and which transcoded.
+#[derive(Debug, Clone)]
+pub struct ContainerPlan {
+    pub extension: String,
+    /// `None` keeps every stream as-is, as when no container was requested.
+    streams: Option<StreamPlan>,
+    /// Streams that are transcoded or dropped, and why.
+    pub conversions: Vec<StreamConversion>,
+}
+
+impl ContainerPlan {
+    /// Keep the input's container and copy every stream.
+    pub fn passthrough(input_path: &str) -> Self {
+        let extension = std::path::Path::new(input_path)
+            .extension()
+            .and_then(|s| s.to_str())
+            .unwrap_or("mp4");
+        ContainerPlan {
+            extension: extension.to_string(),
+            streams: None,
+            conversions: Vec::new(),
+        }
+    }
+
+    /// Default `EncodeOptions`, with codecs swapped for the container's own
+    /// when it cannot hold H.264/AAC (WebM).
+    pub fn default_encode(&self) -> EncodeOptions {
+        let defaults = EncodeOptions::default();
+        match Container::from_extension(&self.extension) {
+            Some(container) if container.check_encode(&defaults).is_err() => EncodeOptions {
+                video_codec: container.default_video_codec(),
+                audio_codec: container.default_audio_codec(),
+                ..defaults
+            },
+            _ => defaults,
+        }
+    }
+
+    /// Whether the input video can be stream-copied into the output.
+    pub fn copies_video(&self) -> bool {
+        self.streams.as_ref().is_none_or(|plan| plan.video.is_none())
+    }
+
+    /// `-map`/`-c` arguments that stream-copy everything the container
+    /// accepts and transcode the rest.
+    pub fn copy_args(&self) -> Vec<String> {
+        let Some(plan) = &self.streams else {
+            return ["-map", "0", "-c", "copy"].map(String::from).to_vec();
+        };
+        let mut args = ["-map", "0:V:0?"].map(String::from).to_vec();
+        match &plan.video {
+            Some(encode) => args.extend(encode.video_args(&self.extension)),
+            None => args.extend(["-c:v".to_string(), "copy".to_string()]),
+        }
+        args.extend(self.audio_subtitle_args(0, None));
+        args
+    }
+
+    /// `-map`/`-c` arguments that re-encode the video, and the audio unless
+    /// `encode` copies it, with `encode`.
+    pub fn encode_args(&self, encode: &EncodeOptions) -> Vec<String> {
+        if self.streams.is_none() {
+            let mut args = vec!["-map".to_string(), "0".to_string()];
+            args.extend(encode.codec_args(&self.extension));
+            args.extend(["-c:s", "copy", "-c:d", "copy"].map(String::from));
+            return args;
+        }
+        let mut args = ["-map", "0:V:0?"].map(String::from).to_vec();
+        args.extend(encode.video_args(&self.extension));
+        let audio = (encode.audio_codec != AudioCodec::Copy).then_some(encode);
+        args.extend(self.audio_subtitle_args(0, audio));
+        args
+    }
+
+    /// `-map`/`-c` arguments for the audio and subtitles of input `input`.
+    /// Audio is encoded with `encode` when given, else copied where possible.
+    pub fn audio_subtitle_args(
+        &self,
+        input: usize,
+        encode: Option<&EncodeOptions>,
+    ) -> Vec<String> {
+        let Some(plan) = &self.streams else {
+            let mut args = vec!["-map".to_string(), format!("{}:a?", input)];
+            args.extend(["-map".to_string(), format!("{}:s?", input)]);
+            match encode {
+                Some(encode) => args.extend(encode.audio_args("a")),
+                None => args.extend(["-c:a".to_string(), "copy".to_string()]),
+            }
+            args.extend(["-c:s".to_string(), "copy".to_string()]);
+            return args;
+        };
+
+        let mut args = Vec::new();
+        for (n, (index, target)) in plan.audio.iter().enumerate() {
+            args.extend(["-map".to_string(), format!("{}:{}", input, index)]);
+            let specifier = format!("a:{}", n);
+            match (encode, target) {
+                (Some(encode), _) => args.extend(encode.audio_args(&specifier)),
+                (None, Some(codec)) => {
+                    args.extend([format!("-c:{}", specifier), codec.encoder().to_string()])
+                }
+                (None, None) => args.extend([format!("-c:{}", specifier), "copy".to_string()]),
+            }
+        }
+        for (n, (index, target)) in plan.subtitles.iter().enumerate() {
+            args.extend(["-map".to_string(), format!("{}:{}", input, index)]);
+            let codec = target.unwrap_or("copy");
+            args.extend([format!("-c:s:{}", n), codec.to_string()]);
+        }
+        args
+    }
+}
+
+/// Compare the input's streams against `container` and decide which can be
+/// stream-copied. Video that cannot is re-encoded with `encode` if the
+/// container accepts its codec, else with the container's default codec.
+pub async fn plan_container(
+    app_handle: &AppHandle,
+    input_path: &str,
+    container: Option<Container>,
+    encode: &EncodeOptions,
+) -> Result<ContainerPlan, AppError> {
+    let passthrough = ContainerPlan::passthrough(input_path);
+    let Some(container) = container else {
+        return Ok(passthrough);
+    };
+    if passthrough.extension.eq_ignore_ascii_case(container.extension()) {
+        return Ok(passthrough);
+    }
+
+    let probe = probe_media(app_handle, input_path).await?;
+    let mut conversions = Vec::new();
+    let mut conversion = |kind, stream_index, codec: &str, target: Option<&str>| {
+        let reason = format!("{} cannot be stored in {}", codec, container.name());
+        conversions.push(StreamConversion {
+            kind,
+            stream_index,
+            codec: codec.to_string(),
+            target: target.map(str::to_string),
+            reason,
+        });
+    };
+
+    let video = match &probe.video {
+        Some(video) if !container.accepts(StreamKind::Video, &video.codec) => {
+            let encode = if container.accepts(StreamKind::Video, encode.video_codec.codec_name()) {
+                encode.clone()
+            } else {
+                EncodeOptions {
+                    video_codec: container.default_video_codec(),
+                    ..Default::default()
+                }
+            };
+            let target = encode.video_codec.encoder();
+            conversion(StreamKind::Video, None, &video.codec, Some(target));
+            Some(encode)
+        }
+        _ => None,
+    };
+
+    let fallback_audio = match encode.audio_codec.codec_name() {
+        Some(codec) if container.accepts(StreamKind::Audio, codec) => encode.audio_codec,
+        _ => container.default_audio_codec(),
+    };
+    let mut audio = Vec::new();
+    for track in &probe.audio_tracks {
+        if container.accepts(StreamKind::Audio, &track.codec) {
+            audio.push((track.index, None));
+        } else {
+            let target = fallback_audio.encoder();
+            conversion(StreamKind::Audio, Some(track.index), &track.codec, Some(target));
+            audio.push((track.index, Some(fallback_audio)));
+        }
+    }
+
+    let mut subtitles = Vec::new();
+    for track in &probe.subtitle_tracks {
+        let codec = track.codec.as_str();
+        if container.accepts(StreamKind::Subtitle, codec) {
+            subtitles.push((track.index, None));
+            continue;
+        }
+        let target = container
+            .text_subtitle_codec()
+            .filter(|_| TEXT_SUBTITLE_CODECS.contains(&codec));
+        conversion(StreamKind::Subtitle, Some(track.index), codec, target);
+        if let Some(target) = target {
+            subtitles.push((track.index, Some(target)));
+        }
+    }
+
+    Ok(ContainerPlan {
+        extension: container.extension().to_string(),
+        streams: Some(StreamPlan {
+            video,
+            audio,
+            subtitles,
+        }),
+        conversions,
+    })
+}
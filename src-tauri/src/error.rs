@@ -0,0 +1,313 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How much of ffmpeg's stderr is kept on an error.
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Stable identifier of an error case, for the frontend to match on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    FfmpegNotFound,
+    ProbeFailed,
+    FfmpegFailed,
+    UnsupportedCodec,
+    DiskFull,
+    PermissionDenied,
+    Timeout,
+    Cancelled,
+    InvalidInput,
+    NotFound,
+    OutputExists,
+    Io,
+    Internal,
+}
+
+impl ErrorCode {
+    fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::FfmpegNotFound => "ffmpeg_not_found",
+            ErrorCode::ProbeFailed => "probe_failed",
+            ErrorCode::FfmpegFailed => "ffmpeg_failed",
+            ErrorCode::UnsupportedCodec => "unsupported_codec",
+            ErrorCode::DiskFull => "disk_full",
+            ErrorCode::PermissionDenied => "permission_denied",
+            ErrorCode::Timeout => "timeout",
+            ErrorCode::Cancelled => "cancelled",
+            ErrorCode::InvalidInput => "invalid_input",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::OutputExists => "output_exists",
+            ErrorCode::Io => "io",
+            ErrorCode::Internal => "internal",
+        }
+    }
+}
+
+/// Error returned by every backend command. Serialized as an object with
+/// `code`, `message_key`, an English `message`, `details` and, for ffmpeg
+/// failures, the last lines of its stderr in `stderr_tail`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "ErrorPayload", from = "ErrorPayload")]
+pub enum AppError {
+    /// The ffmpeg or ffprobe sidecar is missing or could not be started.
+    FfmpegNotFound { details: String },
+    /// ffprobe failed or its output could not be understood.
+    ProbeFailed { details: String, stderr_tail: Option<String> },
+    /// ffmpeg exited with an error not covered by a more specific case.
+    FfmpegFailed { details: String, stderr_tail: Option<String> },
+    /// The codec or encoder is not available or not supported for this operation.
+    UnsupportedCodec { details: String, stderr_tail: Option<String> },
+    DiskFull { details: String, stderr_tail: Option<String> },
+    PermissionDenied { details: String, stderr_tail: Option<String> },
+    Timeout { details: String },
+    /// The job was cancelled by the user.
+    Cancelled,
+    /// A parameter was out of range or the input cannot be split this way.
+    InvalidInput { details: String },
+    NotFound { details: String },
+    /// An output file already exists and the overwrite policy is `fail`.
+    /// `details` is its path.
+    OutputExists { details: String },
+    Io { details: String },
+    Internal { details: String },
+}
+
+impl AppError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::FfmpegNotFound { .. } => ErrorCode::FfmpegNotFound,
+            AppError::ProbeFailed { .. } => ErrorCode::ProbeFailed,
+            AppError::FfmpegFailed { .. } => ErrorCode::FfmpegFailed,
+            AppError::UnsupportedCodec { .. } => ErrorCode::UnsupportedCodec,
+            AppError::DiskFull { .. } => ErrorCode::DiskFull,
+            AppError::PermissionDenied { .. } => ErrorCode::PermissionDenied,
+            AppError::Timeout { .. } => ErrorCode::Timeout,
+            AppError::Cancelled => ErrorCode::Cancelled,
+            AppError::InvalidInput { .. } => ErrorCode::InvalidInput,
+            AppError::NotFound { .. } => ErrorCode::NotFound,
+            AppError::OutputExists { .. } => ErrorCode::OutputExists,
+            AppError::Io { .. } => ErrorCode::Io,
+            AppError::Internal { .. } => ErrorCode::Internal,
+        }
+    }
+
+    fn details(&self) -> Option<&str> {
+        match self {
+            AppError::FfmpegNotFound { details }
+            | AppError::ProbeFailed { details, .. }
+            | AppError::FfmpegFailed { details, .. }
+            | AppError::UnsupportedCodec { details, .. }
+            | AppError::DiskFull { details, .. }
+            | AppError::PermissionDenied { details, .. }
+            | AppError::Timeout { details }
+            | AppError::InvalidInput { details }
+            | AppError::NotFound { details }
+            | AppError::OutputExists { details }
+            | AppError::Io { details }
+            | AppError::Internal { details } => Some(details),
+            AppError::Cancelled => None,
+        }
+    }
+
+    pub fn stderr_tail(&self) -> Option<&str> {
+        match self {
+            AppError::ProbeFailed { stderr_tail, .. }
+            | AppError::FfmpegFailed { stderr_tail, .. }
+            | AppError::UnsupportedCodec { stderr_tail, .. }
+            | AppError::DiskFull { stderr_tail, .. }
+            | AppError::PermissionDenied { stderr_tail, .. } => stderr_tail.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The sidecar could not be located or spawned.
+    pub fn sidecar(tool: &str, e: impl fmt::Display) -> Self {
+        AppError::FfmpegNotFound {
+            details: format!("Failed to run {}: {}", tool, e),
+        }
+    }
+
+    pub fn probe(details: impl fmt::Display) -> Self {
+        AppError::ProbeFailed {
+            details: details.to_string(),
+            stderr_tail: None,
+        }
+    }
+
+    /// ffprobe exited with an error.
+    pub fn probe_failed(stderr: &str) -> Self {
+        AppError::ProbeFailed {
+            details: "ffprobe failed".to_string(),
+            stderr_tail: stderr_tail(stderr),
+        }
+    }
+
+    /// ffmpeg exited with an error. Known causes in its stderr map to their own case.
+    pub fn ffmpeg(details: impl fmt::Display, stderr: &str) -> Self {
+        let details = details.to_string();
+        let stderr_tail = stderr_tail(stderr);
+        let lower = stderr.to_lowercase();
+        if lower.contains("no space left on device") {
+            AppError::DiskFull {
+                details,
+                stderr_tail,
+            }
+        } else if lower.contains("permission denied") || lower.contains("operation not permitted")
+        {
+            AppError::PermissionDenied {
+                details,
+                stderr_tail,
+            }
+        } else if lower.contains("unknown encoder")
+            || lower.contains("encoder not found")
+            || lower.contains("decoder not found")
+            || lower.contains("not currently supported in container")
+            || lower.contains("could not find tag for codec")
+        {
+            AppError::UnsupportedCodec {
+                details,
+                stderr_tail,
+            }
+        } else {
+            AppError::FfmpegFailed {
+                details,
+                stderr_tail,
+            }
+        }
+    }
+
+    /// A filesystem error, with `context` saying what was being done.
+    pub fn io(context: &str, e: std::io::Error) -> Self {
+        let details = format!("{}: {}", context, e);
+        match e.kind() {
+            std::io::ErrorKind::PermissionDenied => AppError::PermissionDenied {
+                details,
+                stderr_tail: None,
+            },
+            std::io::ErrorKind::StorageFull => AppError::DiskFull {
+                details,
+                stderr_tail: None,
+            },
+            std::io::ErrorKind::NotFound => AppError::NotFound { details },
+            _ => AppError::Io { details },
+        }
+    }
+
+    pub fn invalid(details: impl fmt::Display) -> Self {
+        AppError::InvalidInput {
+            details: details.to_string(),
+        }
+    }
+
+    pub fn not_found(details: impl fmt::Display) -> Self {
+        AppError::NotFound {
+            details: details.to_string(),
+        }
+    }
+
+    pub fn internal(details: impl fmt::Display) -> Self {
+        AppError::Internal {
+            details: details.to_string(),
+        }
+    }
+}
+
+/// The last `STDERR_TAIL_LINES` non-empty lines of ffmpeg output.
+fn stderr_tail(stderr: &str) -> Option<String> {
+    let lines = stderr
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return None;
+    }
+    let start = lines.len().saturating_sub(STDERR_TAIL_LINES);
+    Some(lines[start..].join("\n"))
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let summary = match self.code() {
+            ErrorCode::FfmpegNotFound => "FFmpeg is not available",
+            ErrorCode::ProbeFailed => "Could not read media information",
+            ErrorCode::FfmpegFailed => "FFmpeg failed",
+            ErrorCode::UnsupportedCodec => "Unsupported codec",
+            ErrorCode::DiskFull => "Disk is full",
+            ErrorCode::PermissionDenied => "Permission denied",
+            ErrorCode::Timeout => "Timed out",
+            ErrorCode::Cancelled => "Cancelled",
+            ErrorCode::InvalidInput => "Invalid input",
+            ErrorCode::NotFound => "Not found",
+            ErrorCode::OutputExists => "Output file already exists",
+            ErrorCode::Io => "File system error",
+            ErrorCode::Internal => "Internal error",
+        };
+        match self.details() {
+            Some(details) => write!(f, "{}: {}", summary, details),
+            None => write!(f, "{}", summary),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// Wire format of `AppError`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ErrorPayload {
+    code: ErrorCode,
+    /// Key for the frontend's translation table, e.g. `errors.disk_full`.
+    message_key: String,
+    message: String,
+    details: Option<String>,
+    stderr_tail: Option<String>,
+}
+
+impl From<AppError> for ErrorPayload {
+    fn from(error: AppError) -> Self {
+        ErrorPayload {
+            code: error.code(),
+            message_key: format!("errors.{}", error.code().as_str()),
+            message: error.to_string(),
+            details: error.details().map(str::to_string),
+            stderr_tail: error.stderr_tail().map(str::to_string),
+        }
+    }
+}
+
+impl From<ErrorPayload> for AppError {
+    fn from(payload: ErrorPayload) -> Self {
+        let details = payload.details.unwrap_or_default();
+        let stderr_tail = payload.stderr_tail;
+        match payload.code {
+            ErrorCode::FfmpegNotFound => AppError::FfmpegNotFound { details },
+            ErrorCode::ProbeFailed => AppError::ProbeFailed {
+                details,
+                stderr_tail,
+            },
+            ErrorCode::FfmpegFailed => AppError::FfmpegFailed {
+                details,
+                stderr_tail,
+            },
+            ErrorCode::UnsupportedCodec => AppError::UnsupportedCodec {
+                details,
+                stderr_tail,
+            },
+            ErrorCode::DiskFull => AppError::DiskFull {
+                details,
+                stderr_tail,
+            },
+            ErrorCode::PermissionDenied => AppError::PermissionDenied {
+                details,
+                stderr_tail,
+            },
+            ErrorCode::Timeout => AppError::Timeout { details },
+            ErrorCode::Cancelled => AppError::Cancelled,
+            ErrorCode::InvalidInput => AppError::InvalidInput { details },
+            ErrorCode::NotFound => AppError::NotFound { details },
+            ErrorCode::OutputExists => AppError::OutputExists { details },
+            ErrorCode::Io => AppError::Io { details },
+            ErrorCode::Internal => AppError::Internal { details },
+        }
+    }
+}
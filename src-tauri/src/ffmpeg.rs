@@ -1,9 +1,21 @@
+use crate::checkpoint::{self, JobCheckpoint};
+use crate::encode::{
+    available_encoders, plan_container, validate_encode_options, Container, ContainerPlan,
+    EncodeOptions, StreamConversion, StreamKind,
+};
+use crate::error::AppError;
+use crate::naming::{
+    check_name_collisions, resolve_output_paths, sanitize_file_name, today, NameContext,
+    OutputTemplate, OverwritePolicy,
+};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
 use tauri_plugin_shell::ShellExt;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant, SystemTime};
 use tiny_http::{Header, ListenAddr, Response, Server, StatusCode};
@@ -15,23 +27,122 @@ pub struct VideoInfo {
     pub duration_formatted: String,
     pub filename: String,
     pub file_size: u64,
+    pub chapters: Vec<Chapter>,
+    pub format_name: Option<String>,
+    pub bit_rate: Option<u64>,
+    pub video: Option<VideoStreamInfo>,
+    pub audio_tracks: Vec<AudioTrackInfo>,
+    pub subtitle_tracks: Vec<SubtitleTrackInfo>,
+    pub tags: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VideoStreamInfo {
+    pub codec: String,
+    pub profile: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    /// Nominal frame rate (`r_frame_rate`) in frames per second.
+    pub frame_rate: Option<f64>,
+    /// Average frame rate over the stream (`avg_frame_rate`).
+    pub avg_frame_rate: Option<f64>,
+    /// True when the nominal and average frame rates disagree, which usually means VFR.
+    pub is_vfr: bool,
+    /// Display rotation in degrees, from the display matrix or the legacy `rotate` tag.
+    pub rotation: i32,
+    pub pix_fmt: Option<String>,
+    pub bit_rate: Option<u64>,
+    /// Codec level as ffprobe reports it, e.g. `41` for H.264 4.1 or `123` for HEVC 4.1.
+    pub level: Option<i32>,
+    /// `r_frame_rate` as an exact rational such as `30000/1001`.
+    pub frame_rate_ratio: Option<String>,
+    /// Ticks per second of the stream's time base.
+    pub timescale: Option<u32>,
+    pub color_space: Option<String>,
+    pub color_primaries: Option<String>,
+    pub color_transfer: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AudioTrackInfo {
+    pub index: u32,
+    pub codec: String,
+    pub language: Option<String>,
+    pub title: Option<String>,
+    pub channels: u32,
+    pub channel_layout: Option<String>,
+    pub sample_rate: Option<u32>,
+    pub bit_rate: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubtitleTrackInfo {
+    pub index: u32,
+    pub codec: String,
+    pub language: Option<String>,
+    pub title: Option<String>,
+}
+
+/// Everything `probe_media` reads from `ffprobe -show_streams -show_format`.
+#[derive(Debug, Clone)]
+pub struct MediaProbe {
+    pub duration: f64,
+    pub format_name: Option<String>,
+    pub bit_rate: Option<u64>,
+    pub video: Option<VideoStreamInfo>,
+    pub audio_tracks: Vec<AudioTrackInfo>,
+    pub subtitle_tracks: Vec<SubtitleTrackInfo>,
+    pub tags: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Chapter {
+    pub index: u32,
+    pub title: Option<String>,
+    pub start_seconds: f64,
+    pub end_seconds: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SplitProgress {
+    pub job_id: String,
     pub current_segment: u32,
     pub total_segments: u32,
     pub percentage: f64,
     pub current_file: String,
+    pub output_file: Option<String>,
+    pub speed: Option<f64>,
+    pub eta_seconds: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SplitResult {
     pub success: bool,
     pub output_files: Vec<String>,
+    /// The range each output actually covers, after keyframe snapping.
+    /// Only filled in by range-based splits.
+    #[serde(default)]
+    pub output_ranges: Vec<TimeRange>,
+    /// Preview image for each entry of `output_files`, when requested.
+    /// `None` marks an output whose thumbnail could not be generated.
+    #[serde(default)]
+    pub thumbnails: Vec<Option<String>>,
+    /// Streams that were transcoded or dropped to fit the requested container.
+    #[serde(default)]
+    pub conversions: Vec<StreamConversion>,
+    /// Outputs that were not written because the file already existed and the
+    /// overwrite policy is `skip`.
+    #[serde(default)]
+    pub skipped_files: Vec<String>,
     pub error: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SplitCancelled {
+    pub job_id: String,
+    pub removed_files: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FFmpegStatus {
     pub found: bool,
@@ -100,11 +211,11 @@ pub async fn check_ffmpeg(app_handle: &AppHandle) -> FFmpegStatus {
     }
 }
 
-pub async fn get_video_duration(app_handle: &AppHandle, path: &str) -> Result<f64, String> {
+pub async fn get_video_duration(app_handle: &AppHandle, path: &str) -> Result<f64, AppError> {
     let output = app_handle
         .shell()
         .sidecar("ffprobe")
-        .map_err(|e| format!("Failed to locate ffprobe sidecar: {}", e))?
+        .map_err(|e| AppError::sidecar("ffprobe", e))?
         .args([
             "-v",
             "error",
@@ -116,18 +227,363 @@ pub async fn get_video_duration(app_handle: &AppHandle, path: &str) -> Result<f6
         ])
         .output()
         .await
-        .map_err(|e| format!("Failed to run ffprobe: {}", e))?;
+        .map_err(|e| AppError::sidecar("ffprobe", e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("ffprobe failed: {}", stderr));
+        return Err(AppError::probe_failed(&stderr));
     }
 
     let duration_str = String::from_utf8_lossy(&output.stdout);
     duration_str
         .trim()
         .parse::<f64>()
-        .map_err(|e| format!("Failed to parse duration: {}", e))
+        .map_err(|e| AppError::probe(format!("Failed to parse duration: {}", e)))
+}
+
+/// Container duration in seconds and overall bitrate in bits per second, if ffprobe reports one.
+pub async fn get_duration_and_bitrate(
+    app_handle: &AppHandle,
+    path: &str,
+) -> Result<(f64, Option<f64>), AppError> {
+    let output = app_handle
+        .shell()
+        .sidecar("ffprobe")
+        .map_err(|e| AppError::sidecar("ffprobe", e))?
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "format=duration,bit_rate",
+            "-of",
+            "default=noprint_wrappers=1",
+            path,
+        ])
+        .output()
+        .await
+        .map_err(|e| AppError::sidecar("ffprobe", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::probe_failed(&stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut duration = None;
+    let mut bit_rate = None;
+    for line in stdout.lines() {
+        match line.trim().split_once('=') {
+            Some(("duration", value)) => duration = value.parse::<f64>().ok(),
+            Some(("bit_rate", value)) => bit_rate = value.parse::<f64>().ok().filter(|b| *b > 0.0),
+            _ => {}
+        }
+    }
+
+    let duration = duration.ok_or_else(|| AppError::probe("Failed to parse duration"))?;
+    Ok((duration, bit_rate))
+}
+
+/// Probe container and stream details with `ffprobe -show_streams -show_format`.
+pub async fn probe_media(app_handle: &AppHandle, path: &str) -> Result<MediaProbe, AppError> {
+    let output = app_handle
+        .shell()
+        .sidecar("ffprobe")
+        .map_err(|e| AppError::sidecar("ffprobe", e))?
+        .args([
+            "-v",
+            "error",
+            "-show_streams",
+            "-show_format",
+            "-of",
+            "json",
+            path,
+        ])
+        .output()
+        .await
+        .map_err(|e| AppError::sidecar("ffprobe", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::probe_failed(&stderr));
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| AppError::probe(format!("Failed to parse ffprobe output: {}", e)))?;
+    parse_media_probe(&json)
+}
+
+/// ffprobe prints most numbers as JSON strings.
+fn json_number<T: std::str::FromStr>(value: &serde_json::Value) -> Option<T> {
+    match value {
+        serde_json::Value::String(s) => s.parse().ok(),
+        serde_json::Value::Number(n) => n.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+fn json_string(value: &serde_json::Value) -> Option<String> {
+    value
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// ffprobe color properties, with `unknown` and `reserved` treated as unset.
+fn json_color(value: &serde_json::Value) -> Option<String> {
+    json_string(value).filter(|s| s != "unknown" && s != "reserved")
+}
+
+/// Parse a rational like `30000/1001`. `0/0` means unknown.
+fn parse_frame_rate(value: &serde_json::Value) -> Option<f64> {
+    let (num, den) = value.as_str()?.split_once('/')?;
+    let num = num.parse::<f64>().ok()?;
+    let den = den.parse::<f64>().ok()?;
+    if num > 0.0 && den > 0.0 {
+        Some(num / den)
+    } else {
+        None
+    }
+}
+
+fn parse_media_probe(json: &serde_json::Value) -> Result<MediaProbe, AppError> {
+    let format = &json["format"];
+    let duration = json_number::<f64>(&format["duration"])
+        .ok_or_else(|| AppError::probe("Failed to parse duration"))?;
+
+    let mut video = None;
+    let mut audio_tracks = Vec::new();
+    let mut subtitle_tracks = Vec::new();
+    for stream in json["streams"].as_array().into_iter().flatten() {
+        let tags = &stream["tags"];
+        let index = stream["index"].as_u64().unwrap_or(0) as u32;
+        let codec = json_string(&stream["codec_name"]).unwrap_or_else(|| "unknown".to_string());
+        match stream["codec_type"].as_str() {
+            // Cover art shows up as a video stream; skip it.
+            Some("video") if video.is_none() && stream["disposition"]["attached_pic"] != 1 => {
+                let frame_rate = parse_frame_rate(&stream["r_frame_rate"]);
+                let avg_frame_rate = parse_frame_rate(&stream["avg_frame_rate"]);
+                let is_vfr = match (frame_rate, avg_frame_rate) {
+                    (Some(r), Some(avg)) => (r - avg).abs() / r > 0.01,
+                    _ => false,
+                };
+                let rotation = stream["side_data_list"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .find_map(|side_data| json_number::<f64>(&side_data["rotation"]))
+                    .or_else(|| json_number::<f64>(&tags["rotate"]))
+                    .map(|r| r.round() as i32)
+                    .unwrap_or(0);
+
+                video = Some(VideoStreamInfo {
+                    codec,
+                    profile: json_string(&stream["profile"]),
+                    width: stream["width"].as_u64().unwrap_or(0) as u32,
+                    height: stream["height"].as_u64().unwrap_or(0) as u32,
+                    frame_rate,
+                    avg_frame_rate,
+                    is_vfr,
+                    rotation,
+                    pix_fmt: json_string(&stream["pix_fmt"]),
+                    bit_rate: json_number(&stream["bit_rate"]),
+                    level: stream["level"].as_i64().filter(|l| *l > 0).map(|l| l as i32),
+                    frame_rate_ratio: frame_rate
+                        .and_then(|_| json_string(&stream["r_frame_rate"])),
+                    timescale: json_string(&stream["time_base"])
+                        .and_then(|tb| tb.split_once('/')?.1.parse().ok())
+                        .filter(|t| *t > 0),
+                    color_space: json_color(&stream["color_space"]),
+                    color_primaries: json_color(&stream["color_primaries"]),
+                    color_transfer: json_color(&stream["color_transfer"]),
+                });
+            }
+            Some("audio") => audio_tracks.push(AudioTrackInfo {
+                index,
+                codec,
+                language: json_string(&tags["language"]),
+                title: json_string(&tags["title"]),
+                channels: stream["channels"].as_u64().unwrap_or(0) as u32,
+                channel_layout: json_string(&stream["channel_layout"]),
+                sample_rate: json_number(&stream["sample_rate"]),
+                bit_rate: json_number(&stream["bit_rate"]),
+            }),
+            Some("subtitle") => subtitle_tracks.push(SubtitleTrackInfo {
+                index,
+                codec,
+                language: json_string(&tags["language"]),
+                title: json_string(&tags["title"]),
+            }),
+            _ => {}
+        }
+    }
+
+    let tags = format["tags"]
+        .as_object()
+        .map(|tags| {
+            tags.iter()
+                .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(MediaProbe {
+        duration,
+        format_name: json_string(&format["format_name"]),
+        bit_rate: json_number(&format["bit_rate"]),
+        video,
+        audio_tracks,
+        subtitle_tracks,
+        tags,
+    })
+}
+
+/// Read embedded chapter markers with `ffprobe -show_chapters`.
+pub async fn get_chapters(app_handle: &AppHandle, path: &str) -> Result<Vec<Chapter>, AppError> {
+    let output = app_handle
+        .shell()
+        .sidecar("ffprobe")
+        .map_err(|e| AppError::sidecar("ffprobe", e))?
+        .args(["-v", "error", "-show_chapters", "-of", "json", path])
+        .output()
+        .await
+        .map_err(|e| AppError::sidecar("ffprobe", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::probe_failed(&stderr));
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| AppError::probe(format!("Failed to parse ffprobe output: {}", e)))?;
+    let parse_time = |value: &serde_json::Value| value.as_str().and_then(|s| s.parse::<f64>().ok());
+
+    let chapters = json["chapters"]
+        .as_array()
+        .map(|chapters| {
+            chapters
+                .iter()
+                .filter_map(|chapter| {
+                    Some((
+                        parse_time(&chapter["start_time"])?,
+                        parse_time(&chapter["end_time"])?,
+                        chapter["tags"]["title"].as_str(),
+                    ))
+                })
+                .filter(|(start, end, _)| end > start)
+                .enumerate()
+                .map(|(i, (start_seconds, end_seconds, title))| Chapter {
+                    index: i as u32,
+                    title: title.map(|t| t.trim().to_string()).filter(|t| !t.is_empty()),
+                    start_seconds,
+                    end_seconds,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(chapters)
+}
+
+/// Sorted keyframe timestamps of the first video stream, read from packet flags
+/// so nothing has to be decoded.
+pub async fn get_keyframes(app_handle: &AppHandle, path: &str) -> Result<Vec<f64>, AppError> {
+    let output = app_handle
+        .shell()
+        .sidecar("ffprobe")
+        .map_err(|e| AppError::sidecar("ffprobe", e))?
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "v:0",
+            "-show_entries",
+            "packet=pts_time,flags",
+            "-of",
+            "csv=p=0",
+            path,
+        ])
+        .output()
+        .await
+        .map_err(|e| AppError::sidecar("ffprobe", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::probe_failed(&stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut keyframes = stdout
+        .lines()
+        .filter_map(|line| {
+            let (pts_time, flags) = line.trim().split_once(',')?;
+            if !flags.contains('K') {
+                return None;
+            }
+            pts_time.parse::<f64>().ok()
+        })
+        .collect::<Vec<_>>();
+    keyframes.sort_by(|a, b| a.total_cmp(b));
+    keyframes.dedup();
+    Ok(keyframes)
+}
+
+/// Keyframe timestamps, cached as JSON in the temp dir next to the HLS cache.
+/// The cache key matches `prepare_hls_source`, so edits to the file invalidate it.
+pub async fn get_keyframes_cached(
+    app_handle: &AppHandle,
+    path: &str,
+) -> Result<Vec<f64>, AppError> {
+    let metadata =
+        std::fs::metadata(path).map_err(|e| AppError::io("Failed to read input metadata", e))?;
+    let hash = source_cache_key(path, &metadata, ());
+    let cache_dir = std::env::temp_dir().join("video-splitter-keyframes");
+    let cache_path = cache_dir.join(format!("{hash}.json"));
+
+    if let Ok(contents) = std::fs::read(&cache_path) {
+        if let Ok(keyframes) = serde_json::from_slice::<Vec<f64>>(&contents) {
+            return Ok(keyframes);
+        }
+    }
+
+    let keyframes = get_keyframes(app_handle, path).await?;
+    // The cache is only an optimisation, so write failures are ignored.
+    if std::fs::create_dir_all(&cache_dir).is_ok() {
+        if let Ok(contents) = serde_json::to_vec(&keyframes) {
+            let _ = std::fs::write(&cache_path, contents);
+        }
+    }
+    Ok(keyframes)
+}
+
+/// Where `split_video` will cut for a given `segment_duration`. The segment
+/// muxer starts part N+1 at the first keyframe at or after `(N + 1) * segment_duration`.
+pub fn predict_segment_boundaries(
+    keyframes: &[f64],
+    segment_duration: f64,
+    duration: f64,
+) -> Vec<TimeRange> {
+    let mut boundaries = vec![0.0];
+    if segment_duration > 0.0 {
+        let mut next_cut = segment_duration;
+        for keyframe in keyframes.iter().copied().filter(|k| *k < duration) {
+            if keyframe >= next_cut {
+                boundaries.push(keyframe);
+                next_cut = segment_duration * boundaries.len() as f64;
+            }
+        }
+    }
+    boundaries.push(duration);
+
+    boundaries
+        .windows(2)
+        .filter(|pair| pair[1] > pair[0])
+        .map(|pair| TimeRange {
+            start_seconds: pair[0],
+            end_seconds: pair[1],
+            label: None,
+        })
+        .collect()
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -144,16 +600,16 @@ struct HlsServerState {
 
 static HLS_SERVER: OnceLock<HlsServerState> = OnceLock::new();
 
-fn ensure_hls_server() -> Result<&'static HlsServerState, String> {
+fn ensure_hls_server() -> Result<&'static HlsServerState, AppError> {
     if let Some(server) = HLS_SERVER.get() {
         return Ok(server);
     }
 
     let server = Server::http("127.0.0.1:0")
-        .map_err(|e| format!("Failed to start HLS server: {}", e))?;
+        .map_err(|e| AppError::internal(format!("Failed to start HLS server: {}", e)))?;
     let port = match server.server_addr() {
         ListenAddr::IP(addr) => addr.port(),
-        _ => return Err("Failed to determine HLS server port".to_string()),
+        _ => return Err(AppError::internal("Failed to determine HLS server port")),
     };
 
     let dirs = Arc::new(Mutex::new(HashMap::new()));
@@ -170,12 +626,12 @@ fn ensure_hls_server() -> Result<&'static HlsServerState, String> {
     if HLS_SERVER.set(state).is_err() {
         return HLS_SERVER
             .get()
-            .ok_or_else(|| "Failed to initialize HLS server".to_string());
+            .ok_or_else(|| AppError::internal("Failed to initialize HLS server"));
     }
 
     HLS_SERVER
         .get()
-        .ok_or_else(|| "Failed to initialize HLS server".to_string())
+        .ok_or_else(|| AppError::internal("Failed to initialize HLS server"))
 }
 
 fn handle_hls_request(request: tiny_http::Request, dirs: &Arc<Mutex<HashMap<String, std::path::PathBuf>>>) {
@@ -251,12 +707,34 @@ fn handle_hls_request(request: tiny_http::Request, dirs: &Arc<Mutex<HashMap<Stri
     let _ = request.respond(response);
 }
 
-fn register_hls_dir(id: &str, dir: &Path) -> Result<u16, String> {
+/// Size + mtime hash of a file, the same key the preview caches use.
+pub fn file_fingerprint(path: &str) -> Option<String> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some(format!("{:016x}", source_cache_key(path, &metadata, ())))
+}
+
+/// Cache key for data derived from a source file: its path, size and mtime,
+/// plus whatever parameters the derived data depends on.
+fn source_cache_key<T: Hash>(input_path: &str, metadata: &std::fs::Metadata, extra: T) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    input_path.hash(&mut hasher);
+    metadata.len().hash(&mut hasher);
+    extra.hash(&mut hasher);
+    if let Ok(modified) = metadata.modified() {
+        if let Ok(duration) = modified.duration_since(SystemTime::UNIX_EPOCH) {
+            duration.as_secs().hash(&mut hasher);
+            duration.subsec_nanos().hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+fn register_hls_dir(id: &str, dir: &Path) -> Result<u16, AppError> {
     let server = ensure_hls_server()?;
     let mut map = server
         .dirs
         .lock()
-        .map_err(|_| "Failed to lock HLS map".to_string())?;
+        .map_err(|_| AppError::internal("Failed to lock HLS map"))?;
     map.insert(id.to_string(), dir.to_path_buf());
     Ok(server.port)
 }
@@ -280,9 +758,9 @@ pub async fn prepare_hls_source(
     segment_seconds: u64,
     start_seconds: Option<f64>,
     window_seconds: Option<u64>,
-) -> Result<PreviewSource, String> {
+) -> Result<PreviewSource, AppError> {
     let metadata = std::fs::metadata(input_path)
-        .map_err(|e| format!("Failed to read input metadata: {}", e))?;
+        .map_err(|e| AppError::io("Failed to read input metadata", e))?;
     let file_size = metadata.len();
 
     if file_size < min_size_bytes {
@@ -302,19 +780,11 @@ pub async fn prepare_hls_source(
     };
     let aligned_start = (start_seconds / segment_seconds as f64).floor() * segment_seconds as f64;
     let aligned_start_key = (aligned_start * 1000.0).round() as u64;
-    let mut hasher = std::collections::hash_map::DefaultHasher::new();
-    input_path.hash(&mut hasher);
-    file_size.hash(&mut hasher);
-    segment_seconds.hash(&mut hasher);
-    window_seconds.hash(&mut hasher);
-    aligned_start_key.hash(&mut hasher);
-    if let Ok(modified) = metadata.modified() {
-        if let Ok(duration) = modified.duration_since(SystemTime::UNIX_EPOCH) {
-            duration.as_secs().hash(&mut hasher);
-            duration.subsec_nanos().hash(&mut hasher);
-        }
-    }
-    let hash = hasher.finish();
+    let hash = source_cache_key(
+        input_path,
+        &metadata,
+        (segment_seconds, window_seconds, aligned_start_key),
+    );
 
     let hls_dir = std::env::temp_dir()
         .join("video-splitter-hls")
@@ -331,14 +801,14 @@ pub async fn prepare_hls_source(
     }
 
     std::fs::create_dir_all(&hls_dir)
-        .map_err(|e| format!("Failed to create HLS dir: {}", e))?;
+        .map_err(|e| AppError::io("Failed to create HLS dir", e))?;
 
     let server = ensure_hls_server()?;
     let spawn_needed = {
         let mut jobs = server
             .jobs
             .lock()
-            .map_err(|_| "Failed to lock HLS job map".to_string())?;
+            .map_err(|_| AppError::internal("Failed to lock HLS job map"))?;
         match jobs.get(&hls_id) {
             Some(started) if started.elapsed() < Duration::from_secs(30) => false,
             _ => {
@@ -395,11 +865,11 @@ pub async fn prepare_hls_source(
         let _ = app_handle
             .shell()
             .sidecar("ffmpeg")
-            .map_err(|e| format!("Failed to locate ffmpeg sidecar: {}", e))?
+            .map_err(|e| AppError::sidecar("ffmpeg", e))?
             .current_dir(&hls_dir)
             .args(args)
             .spawn()
-            .map_err(|e| format!("Failed to spawn ffmpeg: {}", e))?;
+            .map_err(|e| AppError::sidecar("ffmpeg", e))?;
     }
 
     let start = Instant::now();
@@ -412,7 +882,9 @@ pub async fn prepare_hls_source(
             if let Ok(mut jobs) = server.jobs.lock() {
                 jobs.remove(&hls_id);
             }
-            return Err("HLS 分片生成超时，请重试".to_string());
+            return Err(AppError::Timeout {
+                details: "HLS segment generation timed out".to_string(),
+            });
         }
 
         tokio::time::sleep(Duration::from_millis(200)).await;
@@ -430,194 +902,2766 @@ pub async fn prepare_hls_source(
     })
 }
 
-pub fn format_duration(seconds: f64) -> String {
-    let hours = (seconds / 3600.0).floor() as u32;
-    let minutes = ((seconds % 3600.0) / 60.0).floor() as u32;
-    let secs = (seconds % 60.0).floor() as u32;
-    format!("{:02}:{:02}:{:02}", hours, minutes, secs)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FilmstripFrame {
+    pub start_seconds: f64,
+    pub end_seconds: f64,
+    /// The frame shown for this cue, taken from the middle of it.
+    pub time_seconds: f64,
+    pub x: u32,
+    pub y: u32,
 }
 
-pub async fn split_video(
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Filmstrip {
+    pub sprite_path: String,
+    pub vtt_path: String,
+    pub tile_width: u32,
+    pub tile_height: u32,
+    pub columns: u32,
+    pub rows: u32,
+    pub frames: Vec<FilmstripFrame>,
+}
+
+const FILMSTRIP_COLUMNS: u32 = 10;
+const FILMSTRIP_MAX_FRAMES: u32 = 500;
+
+/// Extract evenly spaced thumbnails into a single sprite sheet with a WebVTT and
+/// JSON index. Pass `count` for N thumbnails or `interval_seconds` for one every X
+/// seconds. Results are cached under a hashed temp dir like the HLS preview.
+pub async fn generate_filmstrip(
     app_handle: &AppHandle,
     input_path: &str,
-    output_dir: &str,
-    segment_duration: u32,
-) -> Result<SplitResult, String> {
-    let total_duration = get_video_duration(app_handle, input_path).await?;
-    let total_segments = (total_duration / segment_duration as f64).ceil() as u32;
+    count: Option<u32>,
+    interval_seconds: Option<f64>,
+    width: u32,
+) -> Result<Filmstrip, AppError> {
+    let metadata = std::fs::metadata(input_path)
+        .map_err(|e| AppError::io("Failed to read input metadata", e))?;
+    let width = width.clamp(32, 640);
+    let interval_key = interval_seconds.map(|i| (i * 1000.0).round() as u64);
+    let hash = source_cache_key(input_path, &metadata, (count, interval_key, width));
+    let cache_dir = std::env::temp_dir()
+        .join("video-splitter-filmstrip")
+        .join(format!("{hash}"));
+    let index_path = cache_dir.join("index.json");
 
-    let path = std::path::Path::new(input_path);
-    let stem = path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("video");
-    let extension = path
-        .extension()
-        .and_then(|s| s.to_str())
-        .unwrap_or("mp4");
+    if let Ok(contents) = std::fs::read(&index_path) {
+        if let Ok(filmstrip) = serde_json::from_slice::<Filmstrip>(&contents) {
+            if Path::new(&filmstrip.sprite_path).exists() {
+                return Ok(filmstrip);
+            }
+        }
+    }
 
-    let progress = SplitProgress {
-        current_segment: 0,
-        total_segments,
-        percentage: 0.0,
-        current_file: "正在切分...".to_string(),
+    let duration = get_video_duration(app_handle, input_path).await?;
+    let step = match (count, interval_seconds) {
+        (Some(count), _) if count > 0 => duration / count.min(FILMSTRIP_MAX_FRAMES) as f64,
+        (_, Some(interval)) if interval > 0.0 => {
+            interval.max(duration / FILMSTRIP_MAX_FRAMES as f64)
+        }
+        _ => return Err(AppError::invalid("Either count or interval_seconds must be positive")),
     };
-    let _ = app_handle.emit("split-progress", &progress);
+    let frame_count = ((duration / step).ceil() as u32).clamp(1, FILMSTRIP_MAX_FRAMES);
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    std::fs::create_dir_all(&cache_dir)
+        .map_err(|e| AppError::io("Failed to create filmstrip dir", e))?;
+
+    let mut frames = Vec::new();
+    for i in 0..frame_count {
+        let start_seconds = step * i as f64;
+        let end_seconds = (start_seconds + step).min(duration);
+        let time_seconds = (start_seconds + end_seconds) / 2.0;
+        let frame_path = cache_dir.join(format!("frame_{:04}.jpg", i));
+
+        // `-ss` before `-i` seeks to the nearest keyframe, which is plenty for a preview.
+        let output = app_handle
+            .shell()
+            .sidecar("ffmpeg")
+            .map_err(|e| AppError::sidecar("ffmpeg", e))?
+            .args([
+                "-y".to_string(),
+                "-ss".to_string(),
+                format!("{:.3}", time_seconds),
+                "-i".to_string(),
+                input_path.to_string(),
+                "-frames:v".to_string(),
+                "1".to_string(),
+                "-vf".to_string(),
+                format!("scale={}:-2", width),
+                "-q:v".to_string(),
+                "5".to_string(),
+                frame_path.to_string_lossy().to_string(),
+            ])
+            .output()
+            .await
+            .map_err(|e| AppError::sidecar("ffmpeg", e))?;
+
+        if !output.status.success() || !frame_path.exists() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let _ = std::fs::remove_dir_all(&cache_dir);
+            return Err(AppError::ffmpeg(format!("FFmpeg failed on thumbnail {}", i + 1), &stderr));
+        }
 
-    let output_pattern = format!("{}/{}_%03d.{}", output_dir, stem, extension);
+        frames.push(FilmstripFrame {
+            start_seconds,
+            end_seconds,
+            time_seconds,
+            x: 0,
+            y: 0,
+        });
+    }
+
+    let first_frame = cache_dir.join("frame_0000.jpg");
+    let frame_probe = probe_media(app_handle, &first_frame.to_string_lossy()).await;
+    let (tile_width, tile_height) = match frame_probe.ok().and_then(|probe| probe.video) {
+        Some(video) => (video.width, video.height),
+        None => {
+            let _ = std::fs::remove_dir_all(&cache_dir);
+            return Err(AppError::probe("Failed to read thumbnail size"));
+        }
+    };
 
+    let columns = frame_count.min(FILMSTRIP_COLUMNS);
+    let rows = frame_count.div_ceil(columns);
+    let sprite_path = cache_dir.join("sprite.jpg");
     let output = app_handle
         .shell()
         .sidecar("ffmpeg")
-        .map_err(|e| format!("Failed to locate ffmpeg sidecar: {}", e))?
+        .map_err(|e| AppError::sidecar("ffmpeg", e))?
+        .current_dir(&cache_dir)
         .args([
-            "-y",
-            "-i",
-            input_path,
-            "-c",
-            "copy",
-            "-map",
-            "0",
-            "-f",
-            "segment",
-            "-segment_time",
-            &segment_duration.to_string(),
-            "-reset_timestamps",
-            "1",
-            "-break_non_keyframes",
-            "0",
-            &output_pattern,
+            "-y".to_string(),
+            "-i".to_string(),
+            "frame_%04d.jpg".to_string(),
+            "-vf".to_string(),
+            format!("tile={}x{}", columns, rows),
+            "-frames:v".to_string(),
+            "1".to_string(),
+            "-q:v".to_string(),
+            "5".to_string(),
+            "sprite.jpg".to_string(),
         ])
         .output()
         .await
-        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+        .map_err(|e| AppError::sidecar("ffmpeg", e))?;
 
-    if !output.status.success() {
+    if !output.status.success() || !sprite_path.exists() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        let first_file = format!("{}/{}_{:03}.{}", output_dir, stem, 0, extension);
-        if !std::path::Path::new(&first_file).exists() {
-            return Err(format!("FFmpeg failed: {}", stderr));
-        }
+        let _ = std::fs::remove_dir_all(&cache_dir);
+        return Err(AppError::ffmpeg("FFmpeg failed to build sprite", &stderr));
     }
 
-    let mut output_files = Vec::new();
-    for i in 0..total_segments + 5 {
-        let file_path = format!("{}/{}_{:03}.{}", output_dir, stem, i, extension);
-        if std::path::Path::new(&file_path).exists() {
-            output_files.push(file_path);
-        } else {
-            break;
-        }
+    let mut vtt = String::from("WEBVTT\n\n");
+    for (i, frame) in frames.iter_mut().enumerate() {
+        frame.x = (i as u32 % columns) * tile_width;
+        frame.y = (i as u32 / columns) * tile_height;
+        vtt.push_str(&format!(
+            "{} --> {}\nsprite.jpg#xywh={},{},{},{}\n\n",
+            format_vtt_timestamp(frame.start_seconds),
+            format_vtt_timestamp(frame.end_seconds),
+            frame.x,
+            frame.y,
+            tile_width,
+            tile_height
+        ));
+    }
+    for i in 0..frame_count {
+        let _ = std::fs::remove_file(cache_dir.join(format!("frame_{:04}.jpg", i)));
     }
 
-    let final_progress = SplitProgress {
-        current_segment: output_files.len() as u32,
-        total_segments: output_files.len() as u32,
-        percentage: 100.0,
-        current_file: "完成".to_string(),
+    let vtt_path = cache_dir.join("thumbnails.vtt");
+    std::fs::write(&vtt_path, vtt).map_err(|e| AppError::io("Failed to write WebVTT index", e))?;
+
+    let filmstrip = Filmstrip {
+        sprite_path: sprite_path.to_string_lossy().to_string(),
+        vtt_path: vtt_path.to_string_lossy().to_string(),
+        tile_width,
+        tile_height,
+        columns,
+        rows,
+        frames,
     };
-    let _ = app_handle.emit("split-progress", &final_progress);
+    let index = serde_json::to_vec(&filmstrip)
+        .map_err(|e| AppError::internal(format!("Failed to serialize filmstrip index: {}", e)))?;
+    std::fs::write(&index_path, index)
+        .map_err(|e| AppError::io("Failed to write filmstrip index", e))?;
 
-    Ok(SplitResult {
-        success: true,
-        output_files,
-        error: None,
-    })
+    Ok(filmstrip)
+}
+
+fn format_vtt_timestamp(seconds: f64) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        (millis / 60_000) % 60,
+        (millis / 1000) % 60,
+        millis % 1000
+    )
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct TimeRange {
-    pub start_seconds: f64,
-    pub end_seconds: f64,
+pub struct Waveform {
+    pub duration: f64,
+    pub buckets: u32,
+    /// Interleaved `[min, max]` pairs per bucket, as signed 16-bit sample values.
+    pub peaks: Vec<i16>,
 }
 
-pub async fn split_video_by_ranges(
+/// Sample rate the audio is decoded at for waveform peaks. Plenty for drawing.
+const WAVEFORM_SAMPLE_RATE: u32 = 8000;
+
+/// Decode the first audio stream to mono 16-bit PCM and reduce it to min/max
+/// peaks per bucket. The PCM is streamed and reduced on the fly, so long files
+/// never sit in memory. Runs as a job so `cancel_split` can stop the decode.
+pub async fn get_waveform(
     app_handle: &AppHandle,
+    job_id: &str,
     input_path: &str,
-    output_dir: &str,
-    ranges: Vec<TimeRange>,
-) -> Result<SplitResult, String> {
-    let path = std::path::Path::new(input_path);
-    let stem = path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("video");
-    let extension = path
-        .extension()
-        .and_then(|s| s.to_str())
-        .unwrap_or("mp4");
-
-    let total_segments = ranges.len() as u32;
-    let mut output_files = Vec::new();
+    buckets: u32,
+) -> Result<Waveform, AppError> {
+    let buckets = buckets.clamp(1, 20_000);
+    let job = SplitJobGuard::register(job_id)?;
+    let duration = get_video_duration(app_handle, input_path).await?;
+    let expected_samples = (duration * WAVEFORM_SAMPLE_RATE as f64).ceil().max(1.0) as u64;
+    let samples_per_bucket = expected_samples.div_ceil(buckets as u64).max(1);
+
+    let (mut rx, child) = app_handle
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| AppError::sidecar("ffmpeg", e))?
+        .set_raw_out(true)
+        .args([
+            "-v".to_string(),
+            "error".to_string(),
+            "-i".to_string(),
+            input_path.to_string(),
+            "-map".to_string(),
+            "0:a:0".to_string(),
+            "-ac".to_string(),
+            "1".to_string(),
+            "-ar".to_string(),
+            WAVEFORM_SAMPLE_RATE.to_string(),
+            "-f".to_string(),
+            "s16le".to_string(),
+            "-".to_string(),
+        ])
+        .spawn()
+        .map_err(|e| AppError::sidecar("ffmpeg", e))?;
+    let pid = job.track_child(child)?;
+
+    // (min, max) pairs start inverted so the first sample sets both.
+    let mut peaks = [i16::MAX, i16::MIN].repeat(buckets as usize);
+    let mut sample_index: u64 = 0;
+    let mut carry: Option<u8> = None;
+    let mut stderr = String::new();
+    let mut success = false;
+    while let Some(event) = rx.recv().await {
+        match event {
+            CommandEvent::Stdout(bytes) => {
+                let mut bytes = bytes.as_slice();
+                let mut push_sample = |sample: i16| {
+                    let bucket = ((sample_index / samples_per_bucket) as usize)
+                        .min(buckets as usize - 1);
+                    peaks[bucket * 2] = peaks[bucket * 2].min(sample);
+                    peaks[bucket * 2 + 1] = peaks[bucket * 2 + 1].max(sample);
+                    sample_index += 1;
+                };
+                // Chunks can split a sample in half; keep the odd byte for the next chunk.
+                if let Some(low) = carry.take() {
+                    if let Some((&high, rest)) = bytes.split_first() {
+                        push_sample(i16::from_le_bytes([low, high]));
+                        bytes = rest;
+                    } else {
+                        carry = Some(low);
+                    }
+                }
+                let mut pairs = bytes.chunks_exact(2);
+                for pair in &mut pairs {
+                    push_sample(i16::from_le_bytes([pair[0], pair[1]]));
+                }
+                if let [odd] = pairs.remainder() {
+                    carry = Some(*odd);
+                }
+            }
+            CommandEvent::Stderr(bytes) => stderr.push_str(&String::from_utf8_lossy(&bytes)),
+            CommandEvent::Error(e) => stderr.push_str(&e),
+            CommandEvent::Terminated(payload) => {
+                success = payload.code == Some(0);
+                break;
+            }
+            _ => {}
+        }
+    }
 
-    for (i, range) in ranges.iter().enumerate() {
-        let progress = SplitProgress {
-            current_segment: i as u32 + 1,
-            total_segments,
-            percentage: ((i as f64) / (total_segments as f64)) * 100.0,
-            current_file: format!("正在切分片段 {}/{}...", i + 1, total_segments),
-        };
-        let _ = app_handle.emit("split-progress", &progress);
+    job.untrack_child(pid);
 
-        let output_file = format!("{}/{}_{:03}.{}", output_dir, stem, i, extension);
-        let start_time = format!("{:.3}", range.start_seconds);
-        let end_time = format!("{:.3}", range.end_seconds);
+    if job.is_cancelled() {
+        return Err(finish_cancelled_job(app_handle, job_id, &[]));
+    }
+    if !success {
+        return Err(AppError::ffmpeg("FFmpeg failed", &stderr));
+    }
+    // Buckets past the end of the audio received no samples.
+    for pair in peaks.chunks_exact_mut(2) {
+        if pair[0] > pair[1] {
+            pair.fill(0);
+        }
+    }
 
-        let output = app_handle
-            .shell()
-            .sidecar("ffmpeg")
-            .map_err(|e| format!("Failed to locate ffmpeg sidecar: {}", e))?
-            .args([
-                "-y",
-                "-i",
-                input_path,
-                "-ss",
-                &start_time,
-                "-to",
-                &end_time,
-                "-map",
-                "0",
-                "-c:v",
-                "libx264",
-                "-c:a",
-                "aac",
-                "-c:s",
-                "copy",
-                "-c:d",
-                "copy",
-                "-preset",
-                "veryfast",
-                "-crf",
-                "18",
-                "-reset_timestamps",
-                "1",
-                &output_file,
-            ])
-            .output()
-            .await
-            .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+    Ok(Waveform {
+        duration,
+        buckets,
+        peaks,
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    Png,
+    #[default]
+    Jpeg,
+    Webp,
+}
+
+impl ImageFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Webp => "webp",
+        }
+    }
+
+    fn codec_args(self) -> Vec<String> {
+        match self {
+            ImageFormat::Png => vec![],
+            ImageFormat::Jpeg => vec!["-q:v".to_string(), "2".to_string()],
+            ImageFormat::Webp => vec![
+                "-c:v".to_string(),
+                "libwebp".to_string(),
+                "-quality".to_string(),
+                "90".to_string(),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExtractedFrame {
+    pub path: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Write the frame at `time_seconds` to `output_path` and return it with its size.
+/// A coarse keyframe seek before `-i` keeps this fast on long files; the `-ss`
+/// after `-i` then decodes forward to the exact frame.
+pub async fn extract_frame_to(
+    app_handle: &AppHandle,
+    input_path: &str,
+    time_seconds: f64,
+    format: ImageFormat,
+    width: Option<u32>,
+    output_path: &Path,
+) -> Result<ExtractedFrame, AppError> {
+    let time_seconds = time_seconds.max(0.0);
+    let coarse_seek = (time_seconds - 5.0).max(0.0);
+
+    let mut args = vec![
+        "-y".to_string(),
+        "-ss".to_string(),
+        format!("{:.3}", coarse_seek),
+        "-i".to_string(),
+        input_path.to_string(),
+        "-ss".to_string(),
+        format!("{:.3}", time_seconds - coarse_seek),
+        "-frames:v".to_string(),
+        "1".to_string(),
+    ];
+    if let Some(width) = width.filter(|w| *w > 0) {
+        args.extend(["-vf".to_string(), format!("scale={}:-2", width)]);
+    }
+    args.extend(format.codec_args());
+    args.push(output_path.to_string_lossy().to_string());
+
+    let output = app_handle
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| AppError::sidecar("ffmpeg", e))?
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| AppError::sidecar("ffmpeg", e))?;
+
+    if !output.status.success() || !output_path.exists() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::ffmpeg("FFmpeg failed to extract frame", &stderr));
+    }
+
+    let path = output_path.to_string_lossy().to_string();
+    let video = probe_media(app_handle, &path)
+        .await?
+        .video
+        .ok_or_else(|| AppError::probe("Failed to read frame size"))?;
+
+    Ok(ExtractedFrame {
+        path,
+        width: video.width,
+        height: video.height,
+    })
+}
+
+/// Extract a frame into `output_dir`, or into a hashed temp cache when no
+/// directory is given. Cached frames are reused.
+pub async fn extract_frame(
+    app_handle: &AppHandle,
+    input_path: &str,
+    time_seconds: f64,
+    format: ImageFormat,
+    width: Option<u32>,
+    output_dir: Option<&str>,
+) -> Result<ExtractedFrame, AppError> {
+    let metadata = std::fs::metadata(input_path)
+        .map_err(|e| AppError::io("Failed to read input metadata", e))?;
+    let millis = (time_seconds.max(0.0) * 1000.0).round() as u64;
+    let stem = Path::new(input_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("video");
+    let file_name = format!("{}_{}ms.{}", stem, millis, format.extension());
+
+    let output_path = match output_dir {
+        Some(dir) => Path::new(dir).join(file_name),
+        None => {
+            let hash = source_cache_key(input_path, &metadata, (millis, width, format));
+            let cache_dir = std::env::temp_dir()
+                .join("video-splitter-frames")
+                .join(format!("{hash}"));
+            std::fs::create_dir_all(&cache_dir)
+                .map_err(|e| AppError::io("Failed to create frame cache dir", e))?;
+            let cached = cache_dir.join(file_name);
+            if cached.exists() {
+                let path = cached.to_string_lossy().to_string();
+                let probed = probe_media(app_handle, &path).await.ok();
+                if let Some(video) = probed.and_then(|p| p.video) {
+                    return Ok(ExtractedFrame {
+                        path,
+                        width: video.width,
+                        height: video.height,
+                    });
+                }
+            }
+            cached
+        }
+    };
+
+    extract_frame_to(
+        app_handle,
+        input_path,
+        time_seconds,
+        format,
+        width,
+        &output_path,
+    )
+    .await
+}
+
+/// Where a segment thumbnail is taken from.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ThumbnailMode {
+    /// The frame in the middle of the segment.
+    #[default]
+    Midpoint,
+    /// The most representative frame picked by ffmpeg's `thumbnail` filter.
+    Interesting,
+}
+
+const THUMBNAIL_WIDTH: u32 = 320;
+const THUMBNAIL_SAMPLE_FRAMES: u32 = 300;
+
+async fn generate_segment_thumbnail(
+    app_handle: &AppHandle,
+    video_path: &str,
+    mode: ThumbnailMode,
+) -> Result<String, AppError> {
+    let metadata = std::fs::metadata(video_path)
+        .map_err(|e| AppError::io("Failed to read output metadata", e))?;
+    let hash = source_cache_key(video_path, &metadata, (mode, THUMBNAIL_WIDTH));
+    let cache_dir = std::env::temp_dir().join("video-splitter-thumbnails");
+    std::fs::create_dir_all(&cache_dir)
+        .map_err(|e| AppError::io("Failed to create thumbnail cache dir", e))?;
+    let thumbnail_path = cache_dir.join(format!("{hash}.jpg"));
+    if thumbnail_path.exists() {
+        return Ok(thumbnail_path.to_string_lossy().to_string());
+    }
+
+    let duration = get_video_duration(app_handle, video_path).await?;
+    match mode {
+        ThumbnailMode::Midpoint => {
+            extract_frame_to(
+                app_handle,
+                video_path,
+                duration / 2.0,
+                ImageFormat::Jpeg,
+                Some(THUMBNAIL_WIDTH),
+                &thumbnail_path,
+            )
+            .await?;
+        }
+        ThumbnailMode::Interesting => {
+            // Skip the first tenth so fade-ins and title cards are less likely to win.
+            let args = vec![
+                "-y".to_string(),
+                "-ss".to_string(),
+                format!("{:.3}", duration * 0.1),
+                "-i".to_string(),
+                video_path.to_string(),
+                "-vf".to_string(),
+                format!(
+                    "thumbnail={},scale={}:-2",
+                    THUMBNAIL_SAMPLE_FRAMES, THUMBNAIL_WIDTH
+                ),
+                "-frames:v".to_string(),
+                "1".to_string(),
+                "-q:v".to_string(),
+                "3".to_string(),
+                thumbnail_path.to_string_lossy().to_string(),
+            ];
+            let output = app_handle
+                .shell()
+                .sidecar("ffmpeg")
+                .map_err(|e| AppError::sidecar("ffmpeg", e))?
+                .args(args)
+                .output()
+                .await
+                .map_err(|e| AppError::sidecar("ffmpeg", e))?;
+            if !output.status.success() || !thumbnail_path.exists() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(AppError::ffmpeg("FFmpeg failed to generate thumbnail", &stderr));
+            }
+        }
+    }
+
+    Ok(thumbnail_path.to_string_lossy().to_string())
+}
+
+/// Fill `result.thumbnails` with one preview image per output file.
+/// Thumbnails are best-effort: a failure leaves `None` for that output
+/// instead of failing the split that already succeeded.
+pub async fn attach_segment_thumbnails(
+    app_handle: &AppHandle,
+    result: &mut SplitResult,
+    mode: ThumbnailMode,
+) {
+    let mut thumbnails = Vec::with_capacity(result.output_files.len());
+    for file in &result.output_files {
+        thumbnails.push(generate_segment_thumbnail(app_handle, file, mode).await.ok());
+    }
+    result.thumbnails = thumbnails;
+}
+
+struct SplitJob {
+    /// Running ffmpeg processes keyed by pid. Range splits may run several at once.
+    children: HashMap<u32, CommandChild>,
+    cancelled: bool,
+    /// Set by `reserve_split` until the split itself registers.
+    reserved: bool,
+}
+
+static SPLIT_JOBS: OnceLock<Mutex<HashMap<String, SplitJob>>> = OnceLock::new();
+static JOB_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn split_jobs() -> &'static Mutex<HashMap<String, SplitJob>> {
+    SPLIT_JOBS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Generate a job id for callers that did not supply one.
+pub fn new_job_id() -> String {
+    let millis = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let seq = JOB_COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("job-{millis}-{seq}")
+}
+
+/// Keeps a job registered for cancellation until it is dropped.
+struct SplitJobGuard {
+    job_id: String,
+}
+
+/// Job ids name checkpoint and scratch files, so only `[A-Za-z0-9_-]` is allowed.
+pub fn check_job_id(job_id: &str) -> Result<(), AppError> {
+    let valid = !job_id.is_empty()
+        && job_id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-');
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::invalid(format!("Invalid job id: {}", job_id)))
+    }
+}
+
+impl SplitJobGuard {
+    fn register(job_id: &str) -> Result<Self, AppError> {
+        check_job_id(job_id)?;
+        let mut jobs = split_jobs()
+            .lock()
+            .map_err(|_| AppError::internal("Failed to lock split job map"))?;
+        match jobs.get_mut(job_id) {
+            // Cancelled while it was only reserved, so it never starts.
+            Some(job) if job.reserved && job.cancelled => {
+                jobs.remove(job_id);
+                return Err(AppError::Cancelled);
+            }
+            Some(job) if job.reserved => job.reserved = false,
+            Some(_) => {
+                return Err(AppError::invalid(format!("Split job {} is already running", job_id)))
+            }
+            None => {
+                jobs.insert(
+                    job_id.to_string(),
+                    SplitJob {
+                        children: HashMap::new(),
+                        cancelled: false,
+                        reserved: false,
+                    },
+                );
+            }
+        }
+        Ok(Self {
+            job_id: job_id.to_string(),
+        })
+    }
+
+    fn is_cancelled(&self) -> bool {
+        split_jobs()
+            .lock()
+            .map(|jobs| jobs.get(&self.job_id).is_some_and(|job| job.cancelled))
+            .unwrap_or(false)
+    }
+
+    /// Hand a spawned ffmpeg to the job so `cancel_split` can kill it. A job that
+    /// is already cancelled kills it right away. Returns the pid to untrack later.
+    fn track_child(&self, child: CommandChild) -> Result<u32, AppError> {
+        let pid = child.pid();
+        let mut jobs = split_jobs()
+            .lock()
+            .map_err(|_| AppError::internal("Failed to lock split job map"))?;
+        if let Some(entry) = jobs.get_mut(&self.job_id) {
+            if entry.cancelled {
+                let _ = child.kill();
+            } else {
+                entry.children.insert(pid, child);
+            }
+        }
+        Ok(pid)
+    }
+
+    fn untrack_child(&self, pid: u32) {
+        if let Ok(mut jobs) = split_jobs().lock() {
+            if let Some(entry) = jobs.get_mut(&self.job_id) {
+                entry.children.remove(&pid);
+            }
+        }
+    }
+}
+
+impl Drop for SplitJobGuard {
+    fn drop(&mut self) {
+        if let Ok(mut jobs) = split_jobs().lock() {
+            jobs.remove(&self.job_id);
+        }
+    }
+}
+
+/// Claim `job_id` for a split that is about to start, so `cancel_split` can stop
+/// it before it registers. The split takes the entry over in `register`.
+pub fn reserve_split(job_id: &str) -> Result<(), AppError> {
+    let mut jobs = split_jobs()
+        .lock()
+        .map_err(|_| AppError::internal("Failed to lock split job map"))?;
+    jobs.entry(job_id.to_string()).or_insert(SplitJob {
+        children: HashMap::new(),
+        cancelled: false,
+        reserved: true,
+    });
+    Ok(())
+}
+
+/// Drop a reservation the split never took over, e.g. because it failed validation.
+pub fn release_split(job_id: &str) {
+    if let Ok(mut jobs) = split_jobs().lock() {
+        if jobs.get(job_id).is_some_and(|job| job.reserved) {
+            jobs.remove(job_id);
+        }
+    }
+}
+
+/// Whether a split with this id is registered or reserved, i.e. still running.
+pub fn is_split_running(job_id: &str) -> bool {
+    split_jobs()
+        .lock()
+        .map(|jobs| jobs.contains_key(job_id))
+        .unwrap_or(false)
+}
+
+/// Kill the ffmpeg processes of a running split job.
+/// Returns `false` when no job with that id is running.
+pub fn cancel_split(job_id: &str) -> Result<bool, AppError> {
+    let children = {
+        let mut jobs = split_jobs()
+            .lock()
+            .map_err(|_| AppError::internal("Failed to lock split job map"))?;
+        match jobs.get_mut(job_id) {
+            Some(job) => {
+                job.cancelled = true;
+                std::mem::take(&mut job.children)
+            }
+            None => return Ok(false),
+        }
+    };
+
+    for child in children.into_values() {
+        child
+            .kill()
+            .map_err(|e| AppError::internal(format!("Failed to kill ffmpeg: {}", e)))?;
+    }
+    Ok(true)
+}
+
+struct FfmpegRun {
+    success: bool,
+    stderr: String,
+}
+
+/// One block of `-progress` output, reported each time ffmpeg writes `progress=...`.
+#[derive(Debug, Default, Clone)]
+struct FfmpegProgress {
+    out_time_seconds: f64,
+    speed: Option<f64>,
+}
+
+impl FfmpegProgress {
+    fn apply_line(&mut self, line: &str) -> bool {
+        let Some((key, value)) = line.trim().split_once('=') else {
+            return false;
+        };
+        match key {
+            // Despite the name, `out_time_ms` is in microseconds, same as `out_time_us`.
+            "out_time_us" | "out_time_ms" => {
+                if let Ok(us) = value.parse::<i64>() {
+                    self.out_time_seconds = us.max(0) as f64 / 1_000_000.0;
+                }
+            }
+            "speed" => {
+                self.speed = value
+                    .trim_end_matches('x')
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|speed| *speed > 0.0);
+            }
+            "progress" => return true,
+            _ => {}
+        }
+        false
+    }
+}
+
+/// Remaining wall-clock seconds at the current encode speed.
+fn estimate_eta(remaining_seconds: f64, speed: Option<f64>) -> Option<f64> {
+    speed.map(|speed| (remaining_seconds.max(0.0) / speed).round())
+}
+
+/// Arguments that make ffmpeg report machine-readable progress on stdout.
+fn progress_args() -> Vec<String> {
+    vec![
+        "-progress".to_string(),
+        "pipe:1".to_string(),
+        "-nostats".to_string(),
+    ]
+}
+
+/// Spawn ffmpeg for a registered job and wait for it to exit.
+/// The child is stored in the job map so `cancel_split` can kill it, and
+/// `on_progress` is called for every `-progress` block ffmpeg writes to stdout.
+async fn run_ffmpeg_job<F: FnMut(&FfmpegProgress)>(
+    app_handle: &AppHandle,
+    job: &SplitJobGuard,
+    args: Vec<String>,
+    mut on_progress: F,
+) -> Result<FfmpegRun, AppError> {
+    let (mut rx, child) = app_handle
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| AppError::sidecar("ffmpeg", e))?
+        .args(args)
+        .spawn()
+        .map_err(|e| AppError::sidecar("ffmpeg", e))?;
+
+    let pid = job.track_child(child)?;
+
+    let mut stderr = String::new();
+    let mut success = false;
+    let mut progress = FfmpegProgress::default();
+    while let Some(event) = rx.recv().await {
+        match event {
+            CommandEvent::Stdout(line) if progress.apply_line(&String::from_utf8_lossy(&line)) => {
+                on_progress(&progress)
+            }
+            CommandEvent::Stderr(line) => stderr.push_str(&String::from_utf8_lossy(&line)),
+            CommandEvent::Error(e) => stderr.push_str(&e),
+            CommandEvent::Terminated(payload) => {
+                success = payload.code == Some(0);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    job.untrack_child(pid);
+
+    Ok(FfmpegRun { success, stderr })
+}
+
+/// Delete outputs left behind by a cancelled job and notify the frontend.
+fn finish_cancelled_job(app_handle: &AppHandle, job_id: &str, files: &[String]) -> AppError {
+    let mut removed_files = Vec::new();
+    for file in files {
+        if std::fs::remove_file(file).is_ok() {
+            removed_files.push(file.clone());
+        }
+    }
+
+    let _ = app_handle.emit(
+        "split-cancelled",
+        &SplitCancelled {
+            job_id: job_id.to_string(),
+            removed_files,
+        },
+    );
+    AppError::Cancelled
+}
+
+/// Stream-copy `input_path` into keyframe-aligned parts with the segment muxer,
+/// transcoding only the streams `plan` says the output container cannot hold.
+/// `segment_flag` is `-segment_time` or `-segment_times`.
+fn segment_copy_args(
+    input_path: &str,
+    plan: &ContainerPlan,
+    segment_flag: &str,
+    segment_value: String,
+    output_pattern: &str,
+) -> Vec<String> {
+    let mut args = vec!["-y".to_string()];
+    args.extend(progress_args());
+    args.extend(["-i".to_string(), input_path.to_string()]);
+    args.extend(plan.copy_args());
+    args.extend([
+        "-f".to_string(),
+        "segment".to_string(),
+        segment_flag.to_string(),
+        segment_value,
+        "-reset_timestamps".to_string(),
+        "1".to_string(),
+        "-break_non_keyframes".to_string(),
+        "0".to_string(),
+        output_pattern.to_string(),
+    ]);
+    args
+}
+
+/// Files named `{stem}_000.{ext}`, `{stem}_001.{ext}`, ... in `dir`, up to the first gap.
+fn numbered_outputs(dir: &Path, stem: &str, extension: &str) -> Vec<std::path::PathBuf> {
+    let mut files = Vec::new();
+    for i in 0.. {
+        let file_path = dir.join(format!("{}_{:03}.{}", stem, i, extension));
+        if !file_path.exists() {
+            break;
+        }
+        files.push(file_path);
+    }
+    files
+}
+
+pub fn format_duration(seconds: f64) -> String {
+    let hours = (seconds / 3600.0).floor() as u32;
+    let minutes = ((seconds % 3600.0) / 60.0).floor() as u32;
+    let secs = (seconds % 60.0).floor() as u32;
+    format!("{:02}:{:02}:{:02}", hours, minutes, secs)
+}
+
+/// Where and how split outputs are written.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct OutputOptions {
+    /// Output container; `None` keeps the input's.
+    pub container: Option<Container>,
+    /// File name template, e.g. `{stem}_{index:04}`. `None` keeps the
+    /// `{stem}_{:03}` naming (labels for range splits).
+    pub name_template: Option<String>,
+    /// What happens to outputs whose file already exists.
+    pub overwrite: OverwritePolicy,
+}
+
+impl OutputOptions {
+    /// Parse the name template of a split whose number of outputs is only
+    /// known once ffmpeg has run, so its names must be distinct by construction.
+    fn numbered_template(&self) -> Result<Option<OutputTemplate>, AppError> {
+        let template = self
+            .name_template
+            .as_deref()
+            .map(OutputTemplate::parse)
+            .transpose()?;
+        if template.as_ref().is_some_and(|t| !t.is_unique_per_output()) {
+            return Err(AppError::invalid(
+                "The output template needs {index} or {start} to give every part its own name",
+            ));
+        }
+        Ok(template)
+    }
+}
+
+/// Scratch dir in the output dir, removed when dropped. Numbered parts are cut
+/// into one so ffmpeg never writes over existing files.
+struct WorkDir(std::path::PathBuf);
+
+impl WorkDir {
+    /// The dir is named after the job, so concurrent jobs splitting inputs with
+    /// the same stem into one output dir never share it.
+    fn create(output_dir: &str, stem: &str, job_id: &str) -> Result<Self, AppError> {
+        let dir = Path::new(output_dir).join(format!(".{}_{}_split", stem, job_id));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| AppError::io("Failed to create work dir", e))?;
+        Ok(WorkDir(dir))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// The `{stem}_%03d.{ext}` pattern ffmpeg's segment muxer writes parts to.
+    fn part_pattern(&self, stem: &str, extension: &str) -> String {
+        self.0
+            .join(format!("{}_%03d.{}", stem, extension))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Parts written so far, in order.
+    fn parts(&self, stem: &str, extension: &str) -> Vec<String> {
+        numbered_outputs(&self.0, stem, extension)
+            .into_iter()
+            .map(|part| part.to_string_lossy().to_string())
+            .collect()
+    }
+}
+
+impl Drop for WorkDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// How the numbered parts of a duration, count or size split are named and
+/// placed in the output dir.
+struct PartNaming<'a> {
+    output_dir: &'a str,
+    stem: &'a str,
+    extension: &'a str,
+    template: Option<OutputTemplate>,
+    overwrite: OverwritePolicy,
+}
+
+impl PartNaming<'_> {
+    /// Final path of each part: rendered from the template, or `{stem}_{:03}`.
+    /// `spans` holds each part's start and end in the input.
+    fn paths(&self, spans: &[(f64, f64)]) -> Result<Vec<String>, AppError> {
+        let date = today();
+        let paths = spans
+            .iter()
+            .enumerate()
+            .map(|(index, (start, end))| {
+                let name = match &self.template {
+                    Some(template) => template.render(&NameContext {
+                        stem: self.stem,
+                        index,
+                        count: spans.len(),
+                        start_seconds: Some(*start),
+                        end_seconds: Some(*end),
+                        label: None,
+                        date: &date,
+                        extension: self.extension,
+                    })?,
+                    None => format!("{}_{:03}.{}", self.stem, index, self.extension),
+                };
+                Ok(format!("{}/{}", self.output_dir, name))
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+        check_name_collisions(&paths)?;
+        Ok(paths)
+    }
+
+    /// Fail before ffmpeg runs if the expected parts would replace the input
+    /// or, under `fail`, an existing file. Parts are assumed to be
+    /// `part_seconds` long; the real ones are checked again by `place`.
+    /// Returns the path each part is expected at, for progress events.
+    fn check_expected(
+        &self,
+        input_path: &str,
+        total_duration: f64,
+        part_seconds: f64,
+    ) -> Result<Vec<String>, AppError> {
+        let count = if part_seconds > 0.0 {
+            (total_duration / part_seconds).ceil().max(1.0) as usize
+        } else {
+            1
+        };
+        let spans = (0..count)
+            .map(|i| {
+                let start = i as f64 * part_seconds;
+                (start, (start + part_seconds).min(total_duration))
+            })
+            .collect::<Vec<_>>();
+        let paths = self.paths(&spans)?;
+        let resolved = resolve_output_paths(input_path, &paths, self.overwrite)?;
+        Ok(paths
+            .into_iter()
+            .zip(resolved)
+            .map(|(planned, target)| target.unwrap_or(planned))
+            .collect())
+    }
+
+    /// Move the parts from the work dir to their final paths, applying the
+    /// overwrite policy. Returns the written paths and those that were skipped.
+    async fn place(
+        &self,
+        app_handle: &AppHandle,
+        input_path: &str,
+        parts: &[String],
+    ) -> Result<(Vec<String>, Vec<String>), AppError> {
+        // Part durations are only probed when the template puts times in the names.
+        let mut spans = vec![(0.0, 0.0); parts.len()];
+        if self.template.as_ref().is_some_and(|t| t.uses_times()) {
+            let mut start = 0.0;
+            for (span, part) in spans.iter_mut().zip(parts) {
+                let end = start + get_video_duration(app_handle, part).await?;
+                *span = (start, end);
+                start = end;
+            }
+        }
+        let planned = self.paths(&spans)?;
+        let resolved = resolve_output_paths(input_path, &planned, self.overwrite)?;
+
+        let (mut written, mut skipped) = (Vec::new(), Vec::new());
+        for ((part, planned), target) in parts.iter().zip(planned).zip(resolved) {
+            match target {
+                Some(target) => {
+                    std::fs::rename(part, &target)
+                        .map_err(|e| AppError::io(&format!("Failed to move {}", target), e))?;
+                    written.push(target);
+                }
+                None => skipped.push(planned),
+            }
+        }
+        Ok((written, skipped))
+    }
+}
+
+pub async fn split_video(
+    app_handle: &AppHandle,
+    job_id: &str,
+    input_path: &str,
+    output_dir: &str,
+    segment_duration: u32,
+    output_options: &OutputOptions,
+) -> Result<SplitResult, AppError> {
+    let template = output_options.numbered_template()?;
+    let job = SplitJobGuard::register(job_id)?;
+    let total_duration = get_video_duration(app_handle, input_path).await?;
+    let total_segments = (total_duration / segment_duration as f64).ceil() as u32;
+
+    let path = std::path::Path::new(input_path);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("video");
+    let plan = plan_container(app_handle, input_path, output_options.container, &Default::default())
+        .await?;
+    let extension = plan.extension.as_str();
+    let naming = PartNaming {
+        output_dir,
+        stem,
+        extension,
+        template,
+        overwrite: output_options.overwrite,
+    };
+    let expected = naming.check_expected(input_path, total_duration, segment_duration as f64)?;
+    let work_dir = WorkDir::create(output_dir, stem, job_id)?;
+
+    let progress = SplitProgress {
+        job_id: job_id.to_string(),
+        current_segment: 0,
+        total_segments,
+        percentage: 0.0,
+        current_file: "正在切分...".to_string(),
+        output_file: None,
+        speed: None,
+        eta_seconds: None,
+    };
+    let _ = app_handle.emit("split-progress", &progress);
+
+    let output_pattern = work_dir.part_pattern(stem, extension);
+
+    let args = segment_copy_args(
+        input_path,
+        &plan,
+        "-segment_time",
+        segment_duration.to_string(),
+        &output_pattern,
+    );
+
+    let output = run_ffmpeg_job(app_handle, &job, args, |p| {
+        let done = p.out_time_seconds.min(total_duration);
+        // The segment muxer cuts on keyframes, so this is only the most likely segment.
+        let index = ((done / segment_duration as f64).floor() as u32)
+            .min(total_segments.saturating_sub(1));
+        let progress = SplitProgress {
+            job_id: job_id.to_string(),
+            current_segment: index + 1,
+            total_segments,
+            percentage: if total_duration > 0.0 {
+                (done / total_duration * 100.0).min(99.9)
+            } else {
+                0.0
+            },
+            current_file: format!("正在切分片段 {}/{}...", index + 1, total_segments),
+            output_file: expected.get(index as usize).cloned(),
+            speed: p.speed,
+            eta_seconds: estimate_eta(total_duration - done, p.speed),
+        };
+        let _ = app_handle.emit("split-progress", &progress);
+    })
+    .await?;
+
+    if job.is_cancelled() {
+        drop(work_dir);
+        return Err(finish_cancelled_job(app_handle, job_id, &[]));
+    }
+
+    let parts = work_dir.parts(stem, extension);
+    if !output.success && parts.is_empty() {
+        return Err(AppError::ffmpeg("FFmpeg failed", &output.stderr));
+    }
+    let (output_files, skipped_files) = naming.place(app_handle, input_path, &parts).await?;
+
+    let final_progress = SplitProgress {
+        job_id: job_id.to_string(),
+        current_segment: output_files.len() as u32,
+        total_segments: output_files.len() as u32,
+        percentage: 100.0,
+        current_file: "完成".to_string(),
+        output_file: None,
+        speed: None,
+        eta_seconds: Some(0.0),
+    };
+    let _ = app_handle.emit("split-progress", &final_progress);
+
+    Ok(SplitResult {
+        success: true,
+        output_files,
+        output_ranges: Vec::new(),
+        thumbnails: Vec::new(),
+        conversions: plan.conversions,
+        skipped_files,
+        error: None,
+    })
+}
+
+/// The `part_count - 1` cut points that divide `total_duration` into equal parts,
+/// as a `-segment_times` list. Fractional, so the last part is never a rounding leftover.
+fn count_segment_times(total_duration: f64, part_count: u32) -> String {
+    let part_seconds = total_duration / part_count.max(1) as f64;
+    (1..part_count)
+        .map(|k| format!("{:.3}", part_seconds * k as f64))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Split into exactly `part_count` parts of (nearly) equal length. When stream
+/// copy cannot hit every cut point the video is re-encoded, which is reported
+/// in `conversions`.
+pub async fn split_video_by_count(
+    app_handle: &AppHandle,
+    job_id: &str,
+    input_path: &str,
+    output_dir: &str,
+    part_count: u32,
+    output_options: &OutputOptions,
+) -> Result<SplitResult, AppError> {
+    if part_count == 0 {
+        return Err(AppError::invalid("part_count must be greater than 0"));
+    }
+    let template = output_options.numbered_template()?;
+
+    let job = SplitJobGuard::register(job_id)?;
+    let total_duration = get_video_duration(app_handle, input_path).await?;
+    let part_seconds = total_duration / part_count as f64;
+
+    let path = std::path::Path::new(input_path);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("video");
+    let mut plan =
+        plan_container(app_handle, input_path, output_options.container, &Default::default())
+            .await?;
+    let extension = plan.extension.clone();
+    let naming = PartNaming {
+        output_dir,
+        stem,
+        extension: &extension,
+        template,
+        overwrite: output_options.overwrite,
+    };
+    let expected = naming.check_expected(input_path, total_duration, part_seconds)?;
+    let work_dir = WorkDir::create(output_dir, stem, job_id)?;
+
+    let segment_times = count_segment_times(total_duration, part_count);
+    // A single part has no cut points; a segment longer than the input keeps it whole.
+    let (segment_flag, segment_value) = if part_count > 1 {
+        ("-segment_times", segment_times.clone())
+    } else {
+        ("-segment_time", format!("{:.3}", total_duration + 1.0))
+    };
+    let output_pattern = work_dir.part_pattern(stem, &extension);
+
+    let emit_progress = |p: &FfmpegProgress, label: &str| {
+        let done = p.out_time_seconds.min(total_duration);
+        let index = if part_seconds > 0.0 {
+            ((done / part_seconds).floor() as u32).min(part_count - 1)
+        } else {
+            0
+        };
+        let progress = SplitProgress {
+            job_id: job_id.to_string(),
+            current_segment: index + 1,
+            total_segments: part_count,
+            percentage: if total_duration > 0.0 {
+                (done / total_duration * 100.0).min(99.9)
+            } else {
+                0.0
+            },
+            current_file: format!("{} {}/{}...", label, index + 1, part_count),
+            output_file: expected.get(index as usize).cloned(),
+            speed: p.speed,
+            eta_seconds: estimate_eta(total_duration - done, p.speed),
+        };
+        let _ = app_handle.emit("split-progress", &progress);
+    };
+    emit_progress(&FfmpegProgress::default(), "正在切分片段");
+
+    let mut parts = Vec::new();
+    let args = segment_copy_args(
+        input_path,
+        &plan,
+        segment_flag,
+        segment_value.clone(),
+        &output_pattern,
+    );
+    let output = run_ffmpeg_job(app_handle, &job, args, |p| {
+        emit_progress(p, "正在切分片段")
+    })
+    .await?;
+    if job.is_cancelled() {
+        drop(work_dir);
+        return Err(finish_cancelled_job(app_handle, job_id, &[]));
+    }
+    if output.success {
+        parts = work_dir.parts(stem, &extension);
+    }
+
+    // Stream copy can only cut on existing keyframes, so two cut points may collapse
+    // into one. Re-encode with keyframes forced at the cut points to get exactly N parts.
+    if parts.len() != part_count as usize {
+        let encode = plan.default_encode();
+        if plan.copies_video() {
+            let codec = probe_media(app_handle, input_path)
+                .await?
+                .video
+                .map_or_else(|| "unknown".to_string(), |video| video.codec);
+            plan.conversions.push(StreamConversion {
+                kind: StreamKind::Video,
+                stream_index: None,
+                codec,
+                target: Some(encode.video_codec.codec_name().to_string()),
+                reason: format!(
+                    "Keyframes do not allow {} parts by stream copy; the video was re-encoded",
+                    part_count
+                ),
+            });
+        }
+
+        let mut args = vec!["-y".to_string()];
+        args.extend(progress_args());
+        args.extend(["-i".to_string(), input_path.to_string()]);
+        args.extend(plan.encode_args(&encode));
+        if part_count > 1 {
+            args.extend(["-force_key_frames".to_string(), segment_times]);
+        }
+        args.extend([
+            "-f".to_string(),
+            "segment".to_string(),
+            segment_flag.to_string(),
+            segment_value,
+            "-reset_timestamps".to_string(),
+            "1".to_string(),
+            output_pattern,
+        ]);
+
+        let output = run_ffmpeg_job(app_handle, &job, args, |p| {
+            emit_progress(p, "正在重新编码片段")
+        })
+        .await?;
+        if job.is_cancelled() {
+            drop(work_dir);
+            return Err(finish_cancelled_job(app_handle, job_id, &[]));
+        }
+        if !output.success {
+            return Err(AppError::ffmpeg("FFmpeg failed", &output.stderr));
+        }
+
+        parts = work_dir.parts(stem, &extension);
+        parts.truncate(part_count as usize);
+        if parts.len() != part_count as usize {
+            return Err(AppError::FfmpegFailed {
+                details: format!(
+                    "Expected {} parts but FFmpeg produced {}",
+                    part_count,
+                    parts.len()
+                ),
+                stderr_tail: None,
+            });
+        }
+    }
+    let (output_files, skipped_files) = naming.place(app_handle, input_path, &parts).await?;
+
+    let final_progress = SplitProgress {
+        job_id: job_id.to_string(),
+        current_segment: part_count,
+        total_segments: part_count,
+        percentage: 100.0,
+        current_file: "完成".to_string(),
+        output_file: None,
+        speed: None,
+        eta_seconds: Some(0.0),
+    };
+    let _ = app_handle.emit("split-progress", &final_progress);
+
+    Ok(SplitResult {
+        success: true,
+        output_files,
+        output_ranges: Vec::new(),
+        thumbnails: Vec::new(),
+        conversions: plan.conversions,
+        skipped_files,
+        error: None,
+    })
+}
+
+/// How many times oversized parts are re-cut before giving up.
+const MAX_SIZE_PASSES: u32 = 4;
+
+/// Split into stream-copied parts that each stay under `max_bytes`.
+pub async fn split_video_by_size(
+    app_handle: &AppHandle,
+    job_id: &str,
+    input_path: &str,
+    output_dir: &str,
+    max_bytes: u64,
+    output_options: &OutputOptions,
+) -> Result<SplitResult, AppError> {
+    if max_bytes == 0 {
+        return Err(AppError::invalid("max_bytes must be greater than 0"));
+    }
+    let template = output_options.numbered_template()?;
+
+    let job = SplitJobGuard::register(job_id)?;
+    let path = std::path::Path::new(input_path);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("video");
+    let plan = plan_container(app_handle, input_path, output_options.container, &Default::default())
+        .await?;
+    let extension = plan.extension.as_str();
+    let naming = PartNaming {
+        output_dir,
+        stem,
+        extension,
+        template,
+        overwrite: output_options.overwrite,
+    };
+    let total_duration = get_video_duration(app_handle, input_path).await?;
+    let file_size = std::fs::metadata(input_path).map(|m| m.len()).unwrap_or(0);
+    let part_seconds = if file_size > 0 {
+        total_duration * max_bytes as f64 / file_size as f64
+    } else {
+        total_duration
+    };
+    naming.check_expected(input_path, total_duration, part_seconds)?;
+
+    // Parts are cut into a scratch dir first so re-cut pieces can be renumbered in order.
+    let work_dir = WorkDir::create(output_dir, stem, job_id)?;
+
+    let parts = match cut_parts_under_size(
+        app_handle,
+        &job,
+        input_path,
+        work_dir.path(),
+        stem,
+        &plan,
+        max_bytes,
+    )
+    .await
+    {
+        Ok(parts) => parts,
+        Err(e) => {
+            drop(work_dir);
+            if job.is_cancelled() {
+                return Err(finish_cancelled_job(app_handle, job_id, &[]));
+            }
+            return Err(e);
+        }
+    };
+
+    let parts = parts
+        .iter()
+        .map(|part| part.to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    let (output_files, skipped_files) = naming.place(app_handle, input_path, &parts).await?;
+    drop(work_dir);
+
+    let final_progress = SplitProgress {
+        job_id: job_id.to_string(),
+        current_segment: output_files.len() as u32,
+        total_segments: output_files.len() as u32,
+        percentage: 100.0,
+        current_file: "完成".to_string(),
+        output_file: None,
+        speed: None,
+        eta_seconds: Some(0.0),
+    };
+    let _ = app_handle.emit("split-progress", &final_progress);
+
+    Ok(SplitResult {
+        success: true,
+        output_files,
+        output_ranges: Vec::new(),
+        thumbnails: Vec::new(),
+        conversions: plan.conversions,
+        skipped_files,
+        error: None,
+    })
+}
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("FFmpeg failed on segment {}: {}", i + 1, stderr));
+async fn cut_parts_under_size(
+    app_handle: &AppHandle,
+    job: &SplitJobGuard,
+    input_path: &str,
+    work_dir: &Path,
+    stem: &str,
+    plan: &ContainerPlan,
+    max_bytes: u64,
+) -> Result<Vec<std::path::PathBuf>, AppError> {
+    let extension = plan.extension.as_str();
+    let (total_duration, bit_rate) = get_duration_and_bitrate(app_handle, input_path).await?;
+    let file_size = std::fs::metadata(input_path)
+        .map_err(|e| AppError::io("Failed to read input metadata", e))?
+        .len();
+    let bytes_per_second = bit_rate
+        .map(|bits| bits / 8.0)
+        .unwrap_or(file_size as f64 / total_duration.max(1.0));
+
+    // Keyframe snapping makes parts run a little long, so aim below the limit.
+    let segment_seconds = (max_bytes as f64 * 0.9 / bytes_per_second).max(1.0);
+    let total_segments = (total_duration / segment_seconds).ceil().max(1.0) as u32;
+
+    let output_pattern = work_dir
+        .join(format!("{}_%03d.{}", stem, extension))
+        .to_string_lossy()
+        .to_string();
+    let args = segment_copy_args(
+        input_path,
+        plan,
+        "-segment_time",
+        format!("{:.3}", segment_seconds),
+        &output_pattern,
+    );
+    let output = run_ffmpeg_job(app_handle, job, args, |p| {
+        let done = p.out_time_seconds.min(total_duration);
+        let index = ((done / segment_seconds).floor() as u32).min(total_segments.saturating_sub(1));
+        let progress = SplitProgress {
+            job_id: job.job_id.clone(),
+            current_segment: index + 1,
+            total_segments,
+            percentage: if total_duration > 0.0 {
+                done / total_duration * 90.0
+            } else {
+                0.0
+            },
+            current_file: format!("正在切分片段 {}/{}...", index + 1, total_segments),
+            output_file: None,
+            speed: p.speed,
+            eta_seconds: estimate_eta(total_duration - done, p.speed),
+        };
+        let _ = app_handle.emit("split-progress", &progress);
+    })
+    .await?;
+
+    if job.is_cancelled() {
+        return Err(AppError::Cancelled);
+    }
+
+    // Parts left by a failed run would not cover the whole input.
+    if !output.success {
+        return Err(AppError::ffmpeg("FFmpeg failed", &output.stderr));
+    }
+    let mut parts = numbered_outputs(work_dir, stem, extension);
+    if parts.is_empty() {
+        return Err(AppError::ffmpeg("FFmpeg wrote no parts", &output.stderr));
+    }
+
+    for pass in 1..=MAX_SIZE_PASSES + 1 {
+        let sizes = parts
+            .iter()
+            .map(|part| std::fs::metadata(part).map(|m| m.len()).unwrap_or(0))
+            .collect::<Vec<_>>();
+        if sizes.iter().all(|size| *size <= max_bytes) {
+            return Ok(parts);
+        }
+        if pass > MAX_SIZE_PASSES {
+            break;
+        }
+
+        let mut next_parts = Vec::new();
+        for (i, (part, size)) in parts.into_iter().zip(sizes).enumerate() {
+            if size <= max_bytes {
+                next_parts.push(part);
+                continue;
+            }
+
+            let progress = SplitProgress {
+                job_id: job.job_id.clone(),
+                current_segment: i as u32 + 1,
+                total_segments,
+                percentage: 90.0,
+                current_file: format!("正在重新切分超出大小的片段 {}...", i + 1),
+                output_file: None,
+                speed: None,
+                eta_seconds: None,
+            };
+            let _ = app_handle.emit("split-progress", &progress);
+
+            let part_str = part.to_string_lossy().to_string();
+            let part_duration = get_video_duration(app_handle, &part_str).await?;
+            let sub_seconds = part_duration * (max_bytes as f64 / size as f64) * 0.9;
+            let sub_stem = format!("{}_p{}_{:03}", stem, pass, i);
+            let sub_pattern = work_dir
+                .join(format!("{}_%03d.{}", sub_stem, extension))
+                .to_string_lossy()
+                .to_string();
+            // Parts are already in the output container, so re-cuts only copy.
+            let args = segment_copy_args(
+                &part_str,
+                &ContainerPlan::passthrough(&part_str),
+                "-segment_time",
+                format!("{:.3}", sub_seconds.max(0.1)),
+                &sub_pattern,
+            );
+            let output = run_ffmpeg_job(app_handle, job, args, |_| {}).await?;
+            if job.is_cancelled() {
+                return Err(AppError::Cancelled);
+            }
+            if !output.success {
+                let details = format!("FFmpeg failed to re-cut part {}", i + 1);
+                return Err(AppError::ffmpeg(details, &output.stderr));
+            }
+
+            let pieces = numbered_outputs(work_dir, &sub_stem, extension);
+            if pieces.len() < 2 {
+                return Err(AppError::invalid(format!(
+                    "Part {} is over the size limit and its keyframes are too far apart to split",
+                    i + 1
+                )));
+            }
+            let _ = std::fs::remove_file(&part);
+            next_parts.extend(pieces);
+        }
+        parts = next_parts;
+    }
+
+    Err(AppError::invalid(format!(
+        "Could not get every part under {} bytes after {} passes",
+        max_bytes, MAX_SIZE_PASSES
+    )))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TimeRange {
+    pub start_seconds: f64,
+    pub end_seconds: f64,
+    /// Names the output file instead of the `{stem}_{:03}` index when set.
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// How `split_video_by_ranges` cuts each range.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CutMode {
+    /// Snap to the nearest keyframes and stream-copy. Fast and lossless.
+    Copy,
+    /// Re-encode the exact range with the configured `EncodeOptions`.
+    #[default]
+    Reencode,
+    /// Stream-copy ranges that already sit on keyframes. Other ranges only
+    /// re-encode the partial GOPs at their edges (H.264/HEVC), or fully otherwise.
+    Smart,
+}
+
+/// How a single range is actually cut once the mode has been applied to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CutMethod {
+    Copy,
+    Reencode,
+    /// Re-encode only the partial GOPs at either end and stream-copy the middle.
+    SmartRender,
+}
+
+/// How far a range end may be from a keyframe and still count as "on" it in smart mode.
+const SMART_SNAP_TOLERANCE: f64 = 0.05;
+
+/// The value in the sorted `points` closest to `t`.
+fn nearest_point(points: &[f64], t: f64) -> Option<f64> {
+    let idx = points.partition_point(|p| *p < t);
+    let after = points.get(idx).copied();
+    let before = idx.checked_sub(1).and_then(|i| points.get(i)).copied();
+    match (before, after) {
+        (Some(b), Some(a)) => Some(if t - b <= a - t { b } else { a }),
+        (b, a) => b.or(a),
+    }
+}
+
+/// Move a range onto keyframes so it can be stream-copied without a broken first GOP.
+/// The end may also land on the end of the file. Without keyframes (audio-only
+/// input) every point can be copied, so the range is only clamped to the file.
+fn snap_range_to_keyframes(range: &TimeRange, keyframes: &[f64], duration: f64) -> TimeRange {
+    if keyframes.is_empty() {
+        return TimeRange {
+            start_seconds: range.start_seconds.clamp(0.0, duration),
+            end_seconds: range.end_seconds.clamp(0.0, duration),
+            label: range.label.clone(),
+        };
+    }
+    let start = nearest_point(keyframes, range.start_seconds).unwrap_or(0.0);
+    let mut ends = keyframes.iter().copied().filter(|k| *k < duration).collect::<Vec<_>>();
+    ends.push(duration);
+    let mut end = nearest_point(&ends, range.end_seconds).unwrap_or(duration);
+    if end <= start {
+        end = ends.iter().copied().find(|k| *k > start).unwrap_or(duration);
+    }
+
+    TimeRange {
+        start_seconds: start,
+        end_seconds: end,
+        label: range.label.clone(),
+    }
+}
+
+/// Output paths for each range, using the sanitized label when there is one
+/// and falling back to `{stem}_{:03}`. Duplicate labels get the index appended.
+fn range_output_files(
+    output_dir: &str,
+    stem: &str,
+    extension: &str,
+    ranges: &[TimeRange],
+) -> Vec<String> {
+    let mut used = std::collections::HashSet::new();
+    ranges
+        .iter()
+        .enumerate()
+        .map(|(i, range)| {
+            let label = range
+                .label
+                .as_deref()
+                .map(sanitize_file_name)
+                .filter(|label| !label.is_empty());
+            let name = match label {
+                Some(label) if used.insert(label.to_lowercase()) => label,
+                Some(label) => format!("{}_{:03}", label, i),
+                None => format!("{}_{:03}", stem, i),
+            };
+            format!("{}/{}.{}", output_dir, name, extension)
+        })
+        .collect()
+}
+
+/// Output paths for each range rendered from `template`. `ranges` are the spans
+/// actually cut, so `{start}` and `{end}` reflect keyframe snapping.
+fn templated_range_output_files(
+    template: &OutputTemplate,
+    output_dir: &str,
+    stem: &str,
+    extension: &str,
+    ranges: &[TimeRange],
+    date: &str,
+) -> Result<Vec<String>, AppError> {
+    let files = ranges
+        .iter()
+        .enumerate()
+        .map(|(index, range)| {
+            let name = template.render(&NameContext {
+                stem,
+                index,
+                count: ranges.len(),
+                start_seconds: Some(range.start_seconds),
+                end_seconds: Some(range.end_seconds),
+                label: range.label.as_deref(),
+                date,
+                extension,
+            })?;
+            Ok(format!("{}/{}", output_dir, name))
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+    check_name_collisions(&files)?;
+    Ok(files)
+}
+
+/// Options for `split_video_by_ranges` beyond the ranges themselves.
+#[derive(Debug, Clone)]
+pub struct RangeSplitOptions {
+    pub mode: CutMode,
+    /// How many ranges are cut at once.
+    pub max_workers: usize,
+    /// Ranges before this index were written by an interrupted run and are kept as-is.
+    pub resume_from: usize,
+    /// Encoder settings for ranges that are re-encoded.
+    pub encode: EncodeOptions,
+    pub output: OutputOptions,
+    /// Output path per range resolved by an interrupted run, `None` for ranges
+    /// it skipped. Reused as-is so a resumed run writes the same files.
+    pub planned_outputs: Option<Vec<Option<String>>>,
+}
+
+impl Default for RangeSplitOptions {
+    fn default() -> Self {
+        Self {
+            mode: CutMode::default(),
+            max_workers: default_range_workers(),
+            resume_from: 0,
+            encode: EncodeOptions::default(),
+            output: OutputOptions::default(),
+            planned_outputs: None,
+        }
+    }
+}
+
+/// Cut each range into its own file. Progress is checkpointed per range so an
+/// interrupted run can be resumed; the checkpoint is dropped once this returns.
+pub async fn split_video_by_ranges(
+    app_handle: &AppHandle,
+    job_id: &str,
+    input_path: &str,
+    output_dir: &str,
+    ranges: Vec<TimeRange>,
+    options: RangeSplitOptions,
+) -> Result<SplitResult, AppError> {
+    // Registering first means the checkpoint removed below is this job's own,
+    // never that of a running job with the same id.
+    let job = Arc::new(SplitJobGuard::register(job_id)?);
+    let outcome = cut_ranges(app_handle, &job, input_path, output_dir, ranges, options).await;
+    checkpoint::finish(app_handle, job_id);
+    outcome
+}
+
+async fn cut_ranges(
+    app_handle: &AppHandle,
+    job: &Arc<SplitJobGuard>,
+    input_path: &str,
+    output_dir: &str,
+    ranges: Vec<TimeRange>,
+    options: RangeSplitOptions,
+) -> Result<SplitResult, AppError> {
+    let job_id = job.job_id.as_str();
+    let RangeSplitOptions {
+        mode,
+        max_workers,
+        resume_from,
+        encode,
+        output,
+        planned_outputs,
+    } = options;
+    let template = output
+        .name_template
+        .as_deref()
+        .map(OutputTemplate::parse)
+        .transpose()?;
+    let container_plan =
+        plan_container(app_handle, input_path, output.container, &encode).await?;
+    // Untouched defaults follow the output container; explicit settings must fit it.
+    let encode = if encode == EncodeOptions::default() {
+        container_plan.default_encode()
+    } else {
+        encode
+    };
+    // Smart mode only saves work when the source video can be copied.
+    let mode = if mode == CutMode::Smart && !container_plan.copies_video() {
+        CutMode::Reencode
+    } else {
+        mode
+    };
+    if mode != CutMode::Copy {
+        if let Some(output_container) = Container::from_extension(&container_plan.extension) {
+            output_container.check_encode(&encode)?;
+        }
+        validate_encode_options(app_handle, &encode).await?;
+    }
+    let path = std::path::Path::new(input_path);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("video");
+    let extension = container_plan.extension.as_str();
+
+    let total_segments = ranges.len() as u32;
+
+    // Decide per range whether it is stream-copied, and which span it will really cover.
+    let mut keyframes = Vec::new();
+    let mut smart_encoder = None;
+    let mut smart_fallback = None;
+    let plans: Vec<(TimeRange, CutMethod)> = if mode == CutMode::Reencode {
+        ranges
+            .iter()
+            .map(|range| (range.clone(), CutMethod::Reencode))
+            .collect()
+    } else {
+        let duration = get_video_duration(app_handle, input_path).await?;
+        keyframes = get_keyframes_cached(app_handle, input_path).await?;
+        if mode == CutMode::Smart {
+            // Without a matching encoder, ranges off keyframes fall back to a validated re-encode.
+            let encoders = available_encoders(app_handle).await?;
+            if let Some(video) = probe_media(app_handle, input_path).await?.video {
+                match smart_render_encoder_args(&video, encoders) {
+                    Ok(encoder) => smart_encoder = Some(encoder),
+                    Err(reason) => {
+                        smart_fallback = Some(StreamConversion {
+                            kind: StreamKind::Video,
+                            stream_index: None,
+                            codec: video.codec.clone(),
+                            target: Some(encode.video_codec.codec_name().to_string()),
+                            reason: format!(
+                                "Smart render cannot match the source ({}); \
+                                 ranges off keyframes were re-encoded in full",
+                                reason
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+        ranges
+            .iter()
+            .map(|range| {
+                let snapped = snap_range_to_keyframes(range, &keyframes, duration);
+                match mode {
+                    CutMode::Smart => {
+                        let on_keyframes = (snapped.start_seconds - range.start_seconds).abs()
+                            <= SMART_SNAP_TOLERANCE
+                            && (snapped.end_seconds - range.end_seconds.min(duration)).abs()
+                                <= SMART_SNAP_TOLERANCE;
+                        if on_keyframes {
+                            (snapped, CutMethod::Copy)
+                        } else if smart_encoder.is_some() {
+                            (range.clone(), CutMethod::SmartRender)
+                        } else {
+                            (range.clone(), CutMethod::Reencode)
+                        }
+                    }
+                    _ => (snapped, CutMethod::Copy),
+                }
+            })
+            .collect()
+    };
+
+    let mut conversions = container_plan.conversions.clone();
+    if plans.iter().any(|(_, method)| *method == CutMethod::Reencode) {
+        conversions.extend(smart_fallback);
+    }
+
+    let cut_spans = plans.iter().map(|(range, _)| range.clone()).collect::<Vec<_>>();
+    let (range_outputs, skipped_files) = match planned_outputs {
+        Some(planned) if planned.len() == ranges.len() => (planned, Vec::new()),
+        _ => {
+            let planned = match &template {
+                Some(template) => templated_range_output_files(
+                    template, output_dir, stem, extension, &cut_spans, &today(),
+                )?,
+                None => range_output_files(output_dir, stem, extension, &ranges),
+            };
+            let resolved = resolve_output_paths(input_path, &planned, output.overwrite)?;
+            let skipped = planned
+                .iter()
+                .zip(&resolved)
+                .filter(|(_, target)| target.is_none())
+                .map(|(path, _)| path.clone())
+                .collect::<Vec<_>>();
+            (resolved, skipped)
+        }
+    };
+    let durations = cut_spans
+        .iter()
+        .map(|range| (range.end_seconds - range.start_seconds).max(0.0))
+        .collect::<Vec<_>>();
+    let resume_from = resume_from.min(plans.len());
+    checkpoint::begin(
+        app_handle,
+        &JobCheckpoint {
+            job_id: job_id.to_string(),
+            input_path: input_path.to_string(),
+            fingerprint: file_fingerprint(input_path),
+            output_dir: output_dir.to_string(),
+            ranges: ranges.clone(),
+            mode,
+            encode: encode.clone(),
+            output,
+            output_files: range_outputs.clone(),
+            segment_durations: durations.clone(),
+            completed: (0..resume_from).collect(),
+        },
+    );
+
+    let tracker = Arc::new(RangeProgressTracker::new(app_handle, job_id, durations));
+    for i in 0..resume_from {
+        tracker.finish(i);
+    }
+    let keyframes = Arc::new(keyframes);
+    let smart_encoder = Arc::new(smart_encoder);
+    let encode = Arc::new(encode);
+    let container_plan = Arc::new(container_plan);
+    let workers = Arc::new(tokio::sync::Semaphore::new(max_workers.max(1)));
+
+    let mut tasks = tokio::task::JoinSet::new();
+    for (i, ((range, method), output_file)) in plans.iter().zip(&range_outputs).enumerate() {
+        if i < resume_from {
+            continue;
+        }
+        let Some(output_file) = output_file else {
+            tracker.finish(i);
+            continue;
+        };
+        let app_handle = app_handle.clone();
+        let job = Arc::clone(job);
+        let tracker = Arc::clone(&tracker);
+        let keyframes = Arc::clone(&keyframes);
+        let smart_encoder = Arc::clone(&smart_encoder);
+        let encode = Arc::clone(&encode);
+        let container_plan = Arc::clone(&container_plan);
+        let workers = Arc::clone(&workers);
+        let input_path = input_path.to_string();
+        let (range, method, output_file) = (range.clone(), *method, output_file.clone());
+
+        tasks.spawn(async move {
+            let Ok(_permit) = workers.acquire_owned().await else {
+                return (i, Ok(None));
+            };
+            // Ranges that never started are skipped once the job is cancelled.
+            if job.is_cancelled() {
+                return (i, Ok(None));
+            }
+
+            let emit_progress = |done: f64, speed: Option<f64>| {
+                tracker.update(i, done, speed, &output_file)
+            };
+            emit_progress(0.0, None);
+
+            let output = match (method, smart_encoder.as_ref()) {
+                (CutMethod::SmartRender, Some(encoder)) => {
+                    smart_render_range(
+                        &app_handle,
+                        &job,
+                        SmartRenderInput {
+                            input_path: &input_path,
+                            index: i,
+                            range: &range,
+                            keyframes: &keyframes,
+                            encoder,
+                            encode: &encode,
+                            container_plan: &container_plan,
+                            output_file: &output_file,
+                        },
+                        &emit_progress,
+                    )
+                    .await
+                }
+                _ => {
+                    let args = if method == CutMethod::Copy {
+                        copy_range_args(&input_path, &range, &container_plan, &output_file)
+                    } else {
+                        let plan = &container_plan;
+                        reencode_range_args(&input_path, &range, plan, &encode, &output_file)
+                    };
+                    run_ffmpeg_job(&app_handle, &job, args, |p| {
+                        emit_progress(p.out_time_seconds, p.speed)
+                    })
+                    .await
+                }
+            };
+            tracker.finish(i);
+            (i, output.map(Some))
+        });
+    }
+
+    let mut succeeded = (0..plans.len()).map(|i| i < resume_from).collect::<Vec<_>>();
+    let mut started = vec![false; plans.len()];
+    let mut failure = None;
+    while let Some(joined) = tasks.join_next().await {
+        let error = match joined {
+            Ok((i, Ok(Some(run)))) => {
+                started[i] = true;
+                succeeded[i] = run.success;
+                if run.success {
+                    checkpoint::mark_segment_done(app_handle, job_id, i);
+                }
+                if run.success || job.is_cancelled() {
+                    None
+                } else {
+                    let details = format!("FFmpeg failed on segment {}", i + 1);
+                    Some(AppError::ffmpeg(details, &run.stderr))
+                }
+            }
+            Ok((_, Ok(None))) => None,
+            Ok((i, Err(e))) => {
+                started[i] = true;
+                Some(e)
+            }
+            Err(e) => Some(AppError::internal(format!("Range worker failed: {}", e))),
+        };
+        // Stop the other workers on the first failure.
+        if let Some(error) = error {
+            if failure.is_none() {
+                let _ = cancel_split(job_id);
+                failure = Some(error);
+            }
+        }
+    }
+
+    if let Some(error) = failure {
+        for (i, output_file) in range_outputs.iter().enumerate() {
+            if let (Some(output_file), true) = (output_file, started[i] && !succeeded[i]) {
+                let _ = std::fs::remove_file(output_file);
+            }
         }
+        return Err(error);
+    }
 
-        if std::path::Path::new(&output_file).exists() {
+    if job.is_cancelled() {
+        let attempted = range_outputs
+            .iter()
+            .zip(&started)
+            .filter(|(_, started)| **started)
+            .filter_map(|(file, _)| file.clone())
+            .collect::<Vec<_>>();
+        return Err(finish_cancelled_job(app_handle, job_id, &attempted));
+    }
+
+    let mut output_files = Vec::new();
+    let mut output_ranges = Vec::new();
+    for ((range, _), output_file) in plans.iter().zip(range_outputs) {
+        if let Some(output_file) = output_file.filter(|file| Path::new(file).exists()) {
             output_files.push(output_file);
+            output_ranges.push(range.clone());
         }
     }
 
     let final_progress = SplitProgress {
+        job_id: job_id.to_string(),
         current_segment: total_segments,
         total_segments,
         percentage: 100.0,
         current_file: "完成".to_string(),
+        output_file: None,
+        speed: None,
+        eta_seconds: Some(0.0),
     };
     let _ = app_handle.emit("split-progress", &final_progress);
 
     Ok(SplitResult {
         success: true,
         output_files,
+        output_ranges,
+        thumbnails: Vec::new(),
+        conversions,
+        skipped_files,
         error: None,
     })
 }
+
+/// How many ranges `split_video_by_ranges` cuts at once when the caller sets no limit.
+pub fn default_range_workers() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+struct RangeProgressState {
+    done: Vec<f64>,
+    speeds: Vec<Option<f64>>,
+    finished: u32,
+}
+
+/// Folds the progress of ranges running in parallel into one `split-progress` stream.
+struct RangeProgressTracker {
+    app_handle: AppHandle,
+    job_id: String,
+    durations: Vec<f64>,
+    total_duration: f64,
+    state: Mutex<RangeProgressState>,
+}
+
+impl RangeProgressTracker {
+    fn new(app_handle: &AppHandle, job_id: &str, durations: Vec<f64>) -> Self {
+        let count = durations.len();
+        Self {
+            app_handle: app_handle.clone(),
+            job_id: job_id.to_string(),
+            total_duration: durations.iter().sum(),
+            durations,
+            state: Mutex::new(RangeProgressState {
+                done: vec![0.0; count],
+                speeds: vec![None; count],
+                finished: 0,
+            }),
+        }
+    }
+
+    fn update(&self, index: usize, done_in_range: f64, speed: Option<f64>, output_file: &str) {
+        let total_segments = self.durations.len() as u32;
+        let progress = {
+            let Ok(mut state) = self.state.lock() else {
+                return;
+            };
+            state.done[index] = done_in_range.clamp(0.0, self.durations[index]);
+            state.speeds[index] = speed;
+            let done: f64 = state.done.iter().sum();
+            // Workers add up: two ranges encoding at 2x each finish the job at 4x.
+            let speed = state.speeds.iter().flatten().copied().reduce(|a, b| a + b);
+            SplitProgress {
+                job_id: self.job_id.clone(),
+                current_segment: (state.finished + 1).min(total_segments),
+                total_segments,
+                percentage: if self.total_duration > 0.0 {
+                    (done / self.total_duration * 100.0).min(99.9)
+                } else {
+                    state.finished as f64 / total_segments as f64 * 100.0
+                },
+                current_file: format!("正在切分片段 {}/{}...", index + 1, total_segments),
+                output_file: Some(output_file.to_string()),
+                speed,
+                eta_seconds: estimate_eta(self.total_duration - done, speed),
+            }
+        };
+        let _ = self.app_handle.emit("split-progress", &progress);
+    }
+
+    fn finish(&self, index: usize) {
+        if let Ok(mut state) = self.state.lock() {
+            state.done[index] = self.durations[index];
+            state.speeds[index] = None;
+            state.finished += 1;
+        }
+    }
+}
+
+/// Frame-accurate cut: output seeking after `-i`, re-encoded with `encode`.
+fn reencode_range_args(
+    input_path: &str,
+    range: &TimeRange,
+    container_plan: &ContainerPlan,
+    encode: &EncodeOptions,
+    output_file: &str,
+) -> Vec<String> {
+    let mut args = vec!["-y".to_string()];
+    args.extend(progress_args());
+    args.extend([
+        "-i".to_string(),
+        input_path.to_string(),
+        "-ss".to_string(),
+        format!("{:.3}", range.start_seconds),
+        "-to".to_string(),
+        format!("{:.3}", range.end_seconds),
+    ]);
+    args.extend(container_plan.encode_args(encode));
+    args.extend([
+        "-reset_timestamps".to_string(),
+        "1".to_string(),
+        output_file.to_string(),
+    ]);
+    args
+}
+
+/// Lossless cut of a keyframe-aligned range: input seeking and stream copy of
+/// every stream the output container accepts.
+fn copy_range_args(
+    input_path: &str,
+    range: &TimeRange,
+    container_plan: &ContainerPlan,
+    output_file: &str,
+) -> Vec<String> {
+    let mut args = vec!["-y".to_string()];
+    args.extend(progress_args());
+    args.extend([
+        "-ss".to_string(),
+        format!("{:.6}", range.start_seconds),
+        "-i".to_string(),
+        input_path.to_string(),
+        "-t".to_string(),
+        format!("{:.6}", range.end_seconds - range.start_seconds),
+    ]);
+    args.extend(container_plan.copy_args());
+    args.extend([
+        "-avoid_negative_ts".to_string(),
+        "make_zero".to_string(),
+        output_file.to_string(),
+    ]);
+    args
+}
+
+/// How the re-encoded pieces of a smart render are made to match the copied ones.
+struct SmartEncoder {
+    /// Encoder arguments for the re-encoded pieces.
+    args: Vec<String>,
+    /// Source track timescale, kept on MP4/MOV outputs so copied packets keep their timing.
+    timescale: Option<u32>,
+}
+
+/// Encoder settings whose output can be concatenated with stream-copied packets
+/// of the source: same codec, profile, level, pixel format, size, frame rate and
+/// color description. Only H.264 and HEVC are supported, since the pieces are
+/// joined as MPEG-TS where parameter sets travel in-band. `Err` says why the
+/// source cannot be matched, e.g. when the bundled ffmpeg lacks the encoder.
+fn smart_render_encoder_args(
+    params: &VideoStreamInfo,
+    available: &HashSet<String>,
+) -> Result<SmartEncoder, String> {
+    let codec = params.codec.as_str();
+    let profile = params.profile.as_deref().map(str::to_lowercase);
+    let (encoder, profile) = match codec {
+        "h264" => {
+            let profile = match profile.as_deref() {
+                Some("baseline") | Some("constrained baseline") => Some("baseline"),
+                Some("main") => Some("main"),
+                Some("high") => Some("high"),
+                Some("high 10") => Some("high10"),
+                Some("high 4:2:2") => Some("high422"),
+                Some(p) if p.starts_with("high 4:4:4") => Some("high444"),
+                _ => None,
+            };
+            ("libx264", profile)
+        }
+        "hevc" => {
+            let profile = match profile.as_deref() {
+                Some("main") => Some("main"),
+                Some("main 10") => Some("main10"),
+                _ => None,
+            };
+            ("libx265", profile)
+        }
+        _ => return Err(format!("{} cannot be smart rendered", codec)),
+    };
+    if !available.contains(encoder) {
+        return Err(format!("{} is not available", encoder));
+    }
+    let Some(profile) = profile else {
+        let profile = params.profile.as_deref().unwrap_or("unknown");
+        return Err(format!("{} profile {} cannot be matched", codec, profile));
+    };
+    let Some(pix_fmt) = &params.pix_fmt else {
+        return Err("unknown pixel format".to_string());
+    };
+    // Rotation is lost in the MPEG-TS pieces and variable frame rates cannot be reproduced.
+    let frame_rate = match &params.frame_rate_ratio {
+        Some(rate) if !params.is_vfr => rate,
+        _ => return Err("variable or unknown frame rate".to_string()),
+    };
+    if params.rotation != 0 {
+        return Err("rotated video".to_string());
+    }
+    if params.width == 0 || params.height == 0 {
+        return Err("unknown frame size".to_string());
+    }
+
+    let mut args = vec![
+        "-c:v".to_string(),
+        encoder.to_string(),
+        "-preset".to_string(),
+        "veryfast".to_string(),
+        "-crf".to_string(),
+        "18".to_string(),
+        "-profile:v".to_string(),
+        profile.to_string(),
+        "-pix_fmt".to_string(),
+        pix_fmt.clone(),
+        "-s:v".to_string(),
+        format!("{}x{}", params.width, params.height),
+        "-r:v".to_string(),
+        frame_rate.clone(),
+    ];
+    match (codec, params.level) {
+        ("h264", Some(level)) => args.extend(["-level:v".to_string(), level.to_string()]),
+        // ffprobe reports HEVC levels as 30 × level; x265 wants 10 × level.
+        ("hevc", Some(level)) => {
+            args.extend(["-x265-params".to_string(), format!("level-idc={}", level / 3)])
+        }
+        _ => {}
+    }
+    for (option, value) in [
+        ("-colorspace", &params.color_space),
+        ("-color_primaries", &params.color_primaries),
+        ("-color_trc", &params.color_transfer),
+    ] {
+        if let Some(value) = value {
+            args.extend([option.to_string(), value.clone()]);
+        }
+    }
+    Ok(SmartEncoder {
+        args,
+        timescale: params.timescale,
+    })
+}
+
+struct SmartRenderInput<'a> {
+    input_path: &'a str,
+    /// Position of the range in the job; keys its scratch dir.
+    index: usize,
+    range: &'a TimeRange,
+    keyframes: &'a [f64],
+    encoder: &'a SmartEncoder,
+    /// Used instead when the range is too short to copy any GOP.
+    encode: &'a EncodeOptions,
+    container_plan: &'a ContainerPlan,
+    output_file: &'a str,
+}
+
+/// Frame-accurate cut that re-encodes only from the requested start to the next
+/// keyframe and from the last keyframe to the requested end. The video pieces are
+/// joined with the concat demuxer and the audio is stream-copied from the source.
+async fn smart_render_range(
+    app_handle: &AppHandle,
+    job: &SplitJobGuard,
+    input: SmartRenderInput<'_>,
+    on_progress: &(dyn Fn(f64, Option<f64>) + Sync),
+) -> Result<FfmpegRun, AppError> {
+    let SmartRenderInput {
+        input_path,
+        index,
+        range,
+        keyframes,
+        encoder,
+        encode,
+        container_plan,
+        output_file,
+    } = input;
+    let start = range.start_seconds;
+    let end = range.end_seconds;
+    let first_key = keyframes
+        .iter()
+        .copied()
+        .find(|k| *k >= start - SMART_SNAP_TOLERANCE);
+    let last_key = keyframes
+        .iter()
+        .copied()
+        .rev()
+        .find(|k| *k <= end + SMART_SNAP_TOLERANCE);
+
+    // Without a whole GOP inside the range there is nothing to copy.
+    let (first_key, last_key) = match (first_key, last_key) {
+        (Some(first), Some(last)) if first < last => (first, last),
+        _ => {
+            let args = reencode_range_args(input_path, range, container_plan, encode, output_file);
+            return run_ffmpeg_job(app_handle, job, args, |p| {
+                on_progress(p.out_time_seconds, p.speed)
+            })
+            .await;
+        }
+    };
+
+    let work_dir = std::env::temp_dir()
+        .join("video-splitter-smartcut")
+        .join(format!("{}-{}", job.job_id, index));
+    std::fs::create_dir_all(&work_dir)
+        .map_err(|e| AppError::io("Failed to create smart cut dir", e))?;
+
+    let mut pieces: Vec<(f64, f64, bool)> = Vec::new();
+    if first_key - start > SMART_SNAP_TOLERANCE {
+        pieces.push((start, first_key, true));
+    }
+    pieces.push((first_key, last_key, false));
+    if end - last_key > SMART_SNAP_TOLERANCE {
+        pieces.push((last_key, end, true));
+    }
+
+    let mut concat_list = String::new();
+    for (n, (piece_start, piece_end, encode)) in pieces.iter().enumerate() {
+        let piece_path = work_dir.join(format!("piece_{}.ts", n));
+        let mut args = vec!["-y".to_string()];
+        args.extend(progress_args());
+        args.extend([
+            "-ss".to_string(),
+            format!("{:.6}", piece_start),
+            "-i".to_string(),
+            input_path.to_string(),
+            "-t".to_string(),
+            format!("{:.6}", piece_end - piece_start),
+            "-map".to_string(),
+            "0:v:0".to_string(),
+            "-an".to_string(),
+        ]);
+        if *encode {
+            args.extend(encoder.args.iter().cloned());
+        } else {
+            args.extend(["-c:v".to_string(), "copy".to_string()]);
+        }
+        args.extend([
+            "-f".to_string(),
+            "mpegts".to_string(),
+            piece_path.to_string_lossy().to_string(),
+        ]);
+
+        let offset = piece_start - start;
+        let output = run_ffmpeg_job(app_handle, job, args, |p| {
+            on_progress(offset + p.out_time_seconds, p.speed)
+        })
+        .await?;
+        if !output.success || job.is_cancelled() {
+            let _ = std::fs::remove_dir_all(&work_dir);
+            return Ok(output);
+        }
+
+        let escaped = piece_path.to_string_lossy().replace('\'', "'\\''");
+        concat_list.push_str(&format!("file '{}'\n", escaped));
+    }
+
+    let list_path = work_dir.join("pieces.txt");
+    if let Err(e) = std::fs::write(&list_path, concat_list) {
+        let _ = std::fs::remove_dir_all(&work_dir);
+        return Err(AppError::io("Failed to write concat list", e));
+    }
+
+    let mut args = vec!["-y".to_string()];
+    args.extend([
+        "-f".to_string(),
+        "concat".to_string(),
+        "-safe".to_string(),
+        "0".to_string(),
+        "-i".to_string(),
+        list_path.to_string_lossy().to_string(),
+        "-ss".to_string(),
+        format!("{:.6}", start),
+        "-t".to_string(),
+        format!("{:.6}", end - start),
+        "-i".to_string(),
+        input_path.to_string(),
+        "-map".to_string(),
+        "0:v".to_string(),
+        "-c:v".to_string(),
+        "copy".to_string(),
+    ]);
+    let mov_output = matches!(
+        Container::from_extension(&container_plan.extension),
+        Some(Container::Mp4 | Container::Mov)
+    );
+    if let (true, Some(timescale)) = (mov_output, encoder.timescale) {
+        args.extend(["-video_track_timescale".to_string(), timescale.to_string()]);
+    }
+    args.extend(container_plan.audio_subtitle_args(1, None));
+    args.extend([
+        "-avoid_negative_ts".to_string(),
+        "make_zero".to_string(),
+        output_file.to_string(),
+    ]);
+    let output = run_ffmpeg_job(app_handle, job, args, |_| {}).await;
+    let _ = std::fs::remove_dir_all(&work_dir);
+    output
+}
+
+/// Turn candidate cut points into consecutive ranges covering `[0, duration]`.
+/// Cuts that would leave a range shorter than `min_segment_seconds` are dropped.
+fn ranges_from_cut_points(
+    mut cut_points: Vec<f64>,
+    duration: f64,
+    min_segment_seconds: f64,
+) -> Vec<TimeRange> {
+    cut_points.retain(|t| t.is_finite());
+    cut_points.sort_by(|a, b| a.total_cmp(b));
+
+    let mut boundaries = vec![0.0];
+    for cut in cut_points {
+        let last = *boundaries.last().unwrap_or(&0.0);
+        if cut - last >= min_segment_seconds && duration - cut >= min_segment_seconds {
+            boundaries.push(cut);
+        }
+    }
+    boundaries.push(duration);
+
+    boundaries
+        .windows(2)
+        .filter(|pair| pair[1] > pair[0])
+        .map(|pair| TimeRange {
+            start_seconds: pair[0],
+            end_seconds: pair[1],
+            label: None,
+        })
+        .collect()
+}
+
+/// Decode `input_path` through an analysis filter as a cancellable job,
+/// reporting progress against `total_duration`, and return ffmpeg's stderr.
+async fn run_analysis_job(
+    app_handle: &AppHandle,
+    job: &SplitJobGuard,
+    filter_args: Vec<String>,
+    input_path: &str,
+    total_duration: f64,
+    label: &str,
+) -> Result<String, AppError> {
+    let mut args = vec!["-hide_banner".to_string()];
+    args.extend(progress_args());
+    args.extend(["-i".to_string(), input_path.to_string()]);
+    args.extend(filter_args);
+    args.extend(["-f".to_string(), "null".to_string(), "-".to_string()]);
+
+    let output = run_ffmpeg_job(app_handle, job, args, |p| {
+        let done = p.out_time_seconds.min(total_duration);
+        let progress = SplitProgress {
+            job_id: job.job_id.clone(),
+            current_segment: 0,
+            total_segments: 0,
+            percentage: if total_duration > 0.0 {
+                (done / total_duration * 100.0).min(99.9)
+            } else {
+                0.0
+            },
+            current_file: label.to_string(),
+            output_file: None,
+            speed: p.speed,
+            eta_seconds: estimate_eta(total_duration - done, p.speed),
+        };
+        let _ = app_handle.emit("split-progress", &progress);
+    })
+    .await?;
+
+    if job.is_cancelled() {
+        return Err(finish_cancelled_job(app_handle, &job.job_id, &[]));
+    }
+    if !output.success {
+        return Err(AppError::ffmpeg("FFmpeg failed", &output.stderr));
+    }
+    Ok(output.stderr)
+}
+
+/// Find camera cuts with the `select='gt(scene,X)'` filter and return them as
+/// ranges for the range editor. `threshold` is the scene score between 0 and 1.
+pub async fn detect_scenes(
+    app_handle: &AppHandle,
+    job_id: &str,
+    input_path: &str,
+    threshold: f64,
+    min_segment_seconds: f64,
+) -> Result<Vec<TimeRange>, AppError> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(AppError::invalid("threshold must be between 0 and 1"));
+    }
+
+    let job = SplitJobGuard::register(job_id)?;
+    let total_duration = get_video_duration(app_handle, input_path).await?;
+    let filter_args = vec![
+        "-an".to_string(),
+        "-sn".to_string(),
+        "-dn".to_string(),
+        "-filter:v".to_string(),
+        format!("select='gt(scene,{})',showinfo", threshold),
+    ];
+    let stderr = run_analysis_job(
+        app_handle,
+        &job,
+        filter_args,
+        input_path,
+        total_duration,
+        "正在检测场景切换...",
+    )
+    .await?;
+
+    let cut_points = stderr
+        .lines()
+        .filter(|line| line.contains("Parsed_showinfo"))
+        .filter_map(|line| {
+            let value = line.split("pts_time:").nth(1)?;
+            value.split_whitespace().next()?.parse::<f64>().ok()
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ranges_from_cut_points(
+        cut_points,
+        total_duration,
+        min_segment_seconds.max(0.0),
+    ))
+}
+
+/// Find pauses with `silencedetect` and return ranges that cut in the middle of
+/// each silence. Silences touching the start or end of the file are not cuts.
+pub async fn detect_silence(
+    app_handle: &AppHandle,
+    job_id: &str,
+    input_path: &str,
+    noise_db: f64,
+    min_silence_seconds: f64,
+) -> Result<Vec<TimeRange>, AppError> {
+    if min_silence_seconds <= 0.0 {
+        return Err(AppError::invalid("min_silence_seconds must be greater than 0"));
+    }
+
+    let job = SplitJobGuard::register(job_id)?;
+    let total_duration = get_video_duration(app_handle, input_path).await?;
+    let filter_args = vec![
+        "-vn".to_string(),
+        "-sn".to_string(),
+        "-dn".to_string(),
+        "-af".to_string(),
+        format!("silencedetect=noise={}dB:d={}", noise_db, min_silence_seconds),
+    ];
+    let stderr = run_analysis_job(
+        app_handle,
+        &job,
+        filter_args,
+        input_path,
+        total_duration,
+        "正在检测静音...",
+    )
+    .await?;
+
+    let mut cut_points = Vec::new();
+    let mut silence_start = None;
+    for line in stderr.lines() {
+        if let Some(value) = line.split("silence_start:").nth(1) {
+            silence_start = value.split_whitespace().next().and_then(|v| v.parse::<f64>().ok());
+        } else if let Some(value) = line.split("silence_end:").nth(1) {
+            let silence_end = value.split_whitespace().next().and_then(|v| v.parse::<f64>().ok());
+            if let (Some(start), Some(end)) = (silence_start.take(), silence_end) {
+                if start > 0.0 && end < total_duration {
+                    cut_points.push((start + end) / 2.0);
+                }
+            }
+        }
+    }
+
+    Ok(ranges_from_cut_points(cut_points, total_duration, 0.0))
+}
+
+/// Split into one output per embedded chapter, named after the chapter title.
+pub async fn split_video_by_chapters(
+    app_handle: &AppHandle,
+    job_id: &str,
+    input_path: &str,
+    output_dir: &str,
+    mode: CutMode,
+    encode: EncodeOptions,
+    output: OutputOptions,
+) -> Result<SplitResult, AppError> {
+    let chapters = get_chapters(app_handle, input_path).await?;
+    if chapters.is_empty() {
+        return Err(AppError::not_found("The video has no chapters"));
+    }
+
+    let ranges = chapters
+        .into_iter()
+        .map(|chapter| TimeRange {
+            start_seconds: chapter.start_seconds,
+            end_seconds: chapter.end_seconds,
+            label: chapter.title,
+        })
+        .collect();
+    let options = RangeSplitOptions {
+        mode,
+        encode,
+        output,
+        ..Default::default()
+    };
+    split_video_by_ranges(app_handle, job_id, input_path, output_dir, ranges, options).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start_seconds: f64, end_seconds: f64) -> TimeRange {
+        TimeRange {
+            start_seconds,
+            end_seconds,
+            label: None,
+        }
+    }
+
+    #[test]
+    fn count_segment_times_are_fractional() {
+        assert_eq!(count_segment_times(100.0, 4), "25.000,50.000,75.000");
+        assert_eq!(count_segment_times(10.0, 3), "3.333,6.667");
+        assert_eq!(count_segment_times(59.94, 2), "29.970");
+    }
+
+    #[test]
+    fn count_segment_times_single_part_has_no_cuts() {
+        assert_eq!(count_segment_times(100.0, 1), "");
+        assert_eq!(count_segment_times(100.0, 0), "");
+    }
+
+    #[test]
+    fn snap_without_keyframes_keeps_range() {
+        let snapped = snap_range_to_keyframes(&range(12.5, 40.25), &[], 100.0);
+        assert_eq!((snapped.start_seconds, snapped.end_seconds), (12.5, 40.25));
+
+        let clamped = snap_range_to_keyframes(&range(-1.0, 120.0), &[], 100.0);
+        assert_eq!((clamped.start_seconds, clamped.end_seconds), (0.0, 100.0));
+    }
+
+    #[test]
+    fn snap_moves_to_nearest_keyframes() {
+        let keyframes = [0.0, 10.0, 20.0, 30.0];
+        let snapped = snap_range_to_keyframes(&range(11.0, 28.0), &keyframes, 35.0);
+        assert_eq!((snapped.start_seconds, snapped.end_seconds), (10.0, 30.0));
+
+        // The end of the file counts as a cut point too.
+        let snapped = snap_range_to_keyframes(&range(21.0, 34.0), &keyframes, 35.0);
+        assert_eq!((snapped.start_seconds, snapped.end_seconds), (20.0, 35.0));
+    }
+
+    #[test]
+    fn snap_never_collapses_range() {
+        // Both ends are nearest to 10.0, so the end moves to the next cut point.
+        let keyframes = [0.0, 10.0, 20.0];
+        let snapped = snap_range_to_keyframes(&range(9.0, 11.0), &keyframes, 25.0);
+        assert_eq!((snapped.start_seconds, snapped.end_seconds), (10.0, 20.0));
+
+        // Past the last keyframe the end falls back to the end of the file.
+        let snapped = snap_range_to_keyframes(&range(19.5, 21.0), &keyframes, 22.0);
+        assert_eq!((snapped.start_seconds, snapped.end_seconds), (20.0, 22.0));
+    }
+}
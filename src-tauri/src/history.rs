@@ -0,0 +1,140 @@
+use crate::error::AppError;
+use crate::ffmpeg::{file_fingerprint, SplitResult, TimeRange};
+use crate::queue::{JobStatus, SplitSpec};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime};
+use tauri::{AppHandle, Manager};
+
+/// Oldest entries are dropped beyond this many.
+const MAX_HISTORY_ENTRIES: usize = 500;
+
+/// Serializes read-modify-write cycles on the history file.
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HistoryEntry {
+    pub id: String,
+    /// Unix timestamp in seconds.
+    pub created_at: u64,
+    pub input_path: String,
+    /// Size + mtime hash of the input when it was split. A re-exported
+    /// master at the same path gets a different fingerprint.
+    pub fingerprint: Option<String>,
+    pub output_dir: String,
+    pub spec: SplitSpec,
+    /// The ranges the outputs actually cover, after keyframe snapping.
+    #[serde(default)]
+    pub ranges: Vec<TimeRange>,
+    #[serde(default)]
+    pub output_files: Vec<String>,
+    /// Wall-clock time the split took, in seconds.
+    pub duration_seconds: f64,
+    pub status: JobStatus,
+    pub error: Option<AppError>,
+}
+
+fn history_path(app_handle: &AppHandle) -> Result<PathBuf, AppError> {
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::internal(format!("Failed to resolve app data dir: {}", e)))?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| AppError::io("Failed to create app data dir", e))?;
+    Ok(dir.join("history.json"))
+}
+
+fn read_entries(path: &PathBuf) -> Vec<HistoryEntry> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn write_entries(path: &PathBuf, entries: &[HistoryEntry]) -> Result<(), AppError> {
+    let json = serde_json::to_string_pretty(entries)
+        .map_err(|e| AppError::internal(format!("Failed to serialize history: {}", e)))?;
+    // Write to a temp file first so a crash never leaves a truncated history.
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json).map_err(|e| AppError::io("Failed to write history", e))?;
+    std::fs::rename(&tmp, path).map_err(|e| AppError::io("Failed to write history", e))
+}
+
+/// Newest first.
+pub fn list_history(app_handle: &AppHandle) -> Result<Vec<HistoryEntry>, AppError> {
+    let path = history_path(app_handle)?;
+    let _lock = HISTORY_LOCK
+        .lock()
+        .map_err(|_| AppError::internal("Failed to lock history"))?;
+    let mut entries = read_entries(&path);
+    entries.reverse();
+    Ok(entries)
+}
+
+pub fn get_history_entry(
+    app_handle: &AppHandle,
+    entry_id: &str,
+) -> Result<HistoryEntry, AppError> {
+    list_history(app_handle)?
+        .into_iter()
+        .find(|entry| entry.id == entry_id)
+        .ok_or_else(|| AppError::not_found(format!("History entry {} not found", entry_id)))
+}
+
+/// Append the outcome of a finished split. History is best-effort and never
+/// turns a successful split into an error.
+pub fn record_split(
+    app_handle: &AppHandle,
+    job_id: &str,
+    input_path: &str,
+    output_dir: &str,
+    spec: &SplitSpec,
+    started: Instant,
+    outcome: &Result<SplitResult, AppError>,
+) {
+    let (status, ranges, output_files, error) = match outcome {
+        Ok(result) => (
+            JobStatus::Completed,
+            result.output_ranges.clone(),
+            result.output_files.clone(),
+            None,
+        ),
+        Err(AppError::Cancelled) => (JobStatus::Cancelled, Vec::new(), Vec::new(), None),
+        Err(e) => (JobStatus::Failed, Vec::new(), Vec::new(), Some(e.clone())),
+    };
+    let ranges = match (ranges.is_empty(), spec) {
+        (true, SplitSpec::Ranges { ranges, .. }) => ranges.clone(),
+        _ => ranges,
+    };
+
+    let entry = HistoryEntry {
+        id: job_id.to_string(),
+        created_at: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        input_path: input_path.to_string(),
+        fingerprint: file_fingerprint(input_path),
+        output_dir: output_dir.to_string(),
+        spec: spec.clone(),
+        ranges,
+        output_files,
+        duration_seconds: started.elapsed().as_secs_f64(),
+        status,
+        error,
+    };
+
+    let Ok(path) = history_path(app_handle) else {
+        return;
+    };
+    let Ok(_lock) = HISTORY_LOCK.lock() else {
+        return;
+    };
+    let mut entries = read_entries(&path);
+    entries.retain(|e| e.id != entry.id);
+    entries.push(entry);
+    let overflow = entries.len().saturating_sub(MAX_HISTORY_ENTRIES);
+    entries.drain(..overflow);
+    let _ = write_entries(&path, &entries);
+}
@@ -1,7 +1,15 @@
+mod checkpoint;
 mod commands;
+pub mod encode;
+pub mod error;
 pub mod ffmpeg;
+mod history;
+mod naming;
+mod presets;
+mod queue;
 
-use commands::{allow_asset_path, check_ffmpeg_command, get_video_info, prepare_hls_source_command, select_directory, split_video_command, split_video_by_ranges_command};
+use commands::{allow_asset_path, cancel_split_command, check_ffmpeg_command, delete_preset, detect_scenes_command, detect_silence_command, discard_unfinished_job, enqueue_job, extract_frame_command, generate_filmstrip_command, get_encoder_support, get_keyframes_command, get_video_info, get_waveform_command, list_history, list_jobs, list_presets, list_unfinished_jobs, predict_segments_command, prepare_hls_source_command, remove_job, reorder_jobs, rerun_history_entry, resume_unfinished_job, save_preset, select_directory, set_queue_concurrency, split_video_command, split_video_by_chapters_command, split_video_by_count_command, split_video_by_ranges_command, split_video_by_size_command};
+use queue::JobQueue;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -9,13 +17,39 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
+        .manage(JobQueue::new(1))
         .invoke_handler(tauri::generate_handler![
             allow_asset_path,
+            cancel_split_command,
             check_ffmpeg_command,
+            delete_preset,
+            detect_scenes_command,
+            detect_silence_command,
+            discard_unfinished_job,
+            enqueue_job,
+            extract_frame_command,
+            generate_filmstrip_command,
+            get_encoder_support,
+            get_keyframes_command,
             get_video_info,
+            get_waveform_command,
+            list_history,
+            list_jobs,
+            list_presets,
+            list_unfinished_jobs,
+            predict_segments_command,
             prepare_hls_source_command,
+            remove_job,
+            reorder_jobs,
+            rerun_history_entry,
+            resume_unfinished_job,
+            save_preset,
+            set_queue_concurrency,
             split_video_command,
+            split_video_by_chapters_command,
+            split_video_by_count_command,
             split_video_by_ranges_command,
+            split_video_by_size_command,
             select_directory
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,323 @@
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Longest a sanitized name, or a stem or label put into a template, may be in characters.
+const MAX_NAME_CHARS: usize = 120;
+
+/// Tokens accepted in output file name templates.
+pub const TEMPLATE_TOKENS: &[&str] = &[
+    "stem", "index", "start", "end", "duration", "label", "date", "ext",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(String),
+    Stem,
+    /// Zero-padded to `width` digits, or to the digit count of the last index.
+    Index { width: Option<usize> },
+    Start,
+    End,
+    Duration,
+    Label,
+    Date,
+    Ext,
+}
+
+/// Values one output's name is rendered from.
+pub struct NameContext<'a> {
+    pub stem: &'a str,
+    /// Zero-based position of the output in the job.
+    pub index: usize,
+    /// Number of outputs in the job.
+    pub count: usize,
+    pub start_seconds: Option<f64>,
+    pub end_seconds: Option<f64>,
+    pub label: Option<&'a str>,
+    /// `YYYY-MM-DD`, the day the job started.
+    pub date: &'a str,
+    pub extension: &'a str,
+}
+
+/// A parsed output file name template such as `{stem}_{index:04}` or
+/// `{date}_{label}_{start}`. `.{ext}` is appended unless the template ends with it.
+#[derive(Debug, Clone)]
+pub struct OutputTemplate {
+    tokens: Vec<Token>,
+}
+
+impl OutputTemplate {
+    pub fn parse(template: &str) -> Result<Self, AppError> {
+        let mut tokens = Vec::new();
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            if open > 0 {
+                tokens.push(Token::Literal(rest[..open].to_string()));
+            }
+            let close = rest[open..].find('}').map(|i| open + i).ok_or_else(|| {
+                AppError::invalid(format!("Unclosed {{ in output template: {}", template))
+            })?;
+            tokens.push(parse_token(&rest[open + 1..close])?);
+            rest = &rest[close + 1..];
+        }
+        if !rest.is_empty() {
+            tokens.push(Token::Literal(rest.to_string()));
+        }
+        if tokens.iter().all(|token| matches!(token, Token::Literal(_) | Token::Ext)) {
+            return Err(AppError::invalid(format!(
+                "Output template has no tokens, so every part would get the same name: {}",
+                template
+            )));
+        }
+        Ok(OutputTemplate { tokens })
+    }
+
+    /// Whether rendering needs the start and end of each output.
+    pub fn uses_times(&self) -> bool {
+        self.tokens
+            .iter()
+            .any(|token| matches!(token, Token::Start | Token::End | Token::Duration))
+    }
+
+    /// Whether every output of a job is guaranteed a distinct name.
+    pub fn is_unique_per_output(&self) -> bool {
+        self.tokens
+            .iter()
+            .any(|token| matches!(token, Token::Index { .. } | Token::Start))
+    }
+
+    /// The sanitized file name for one output, including its extension.
+    pub fn render(&self, context: &NameContext) -> Result<String, AppError> {
+        let default_width = context.count.saturating_sub(1).to_string().len().max(3);
+        let mut name = String::new();
+        for token in &self.tokens {
+            match token {
+                Token::Literal(text) => name.push_str(text),
+                Token::Stem => name.extend(context.stem.chars().take(MAX_NAME_CHARS)),
+                Token::Index { width } => {
+                    let width = width.unwrap_or(default_width);
+                    name.push_str(&format!("{:0width$}", context.index, width = width));
+                }
+                Token::Start => name.push_str(&format_time_token(context.start_seconds)),
+                Token::End => name.push_str(&format_time_token(context.end_seconds)),
+                Token::Duration => {
+                    let duration = context
+                        .start_seconds
+                        .zip(context.end_seconds)
+                        .map(|(start, end)| (end - start).max(0.0));
+                    name.push_str(&format_time_token(duration));
+                }
+                Token::Label => {
+                    let label = context.label.unwrap_or_default();
+                    name.extend(label.chars().take(MAX_NAME_CHARS));
+                }
+                Token::Date => name.push_str(context.date),
+                Token::Ext => name.push_str(context.extension),
+            }
+        }
+
+        let suffix = format!(".{}", context.extension);
+        let base = name.strip_suffix(&suffix).unwrap_or(&name);
+        // Only the stem and label were shortened, so the index and times survive.
+        let base = avoid_reserved_name(replace_invalid_chars(base));
+        if base.is_empty() {
+            return Err(AppError::invalid(format!(
+                "Output template gives part {} an empty file name",
+                context.index + 1
+            )));
+        }
+        Ok(format!("{}{}", base, suffix))
+    }
+}
+
+fn parse_token(token: &str) -> Result<Token, AppError> {
+    let (name, spec) = match token.split_once(':') {
+        Some((name, spec)) => (name, Some(spec)),
+        None => (token, None),
+    };
+    let parsed = match (name, spec) {
+        ("stem", None) => Token::Stem,
+        ("index", None) => Token::Index { width: None },
+        ("index", Some(spec)) => {
+            let width = spec
+                .parse::<usize>()
+                .ok()
+                .filter(|width| (1..=9).contains(width))
+                .ok_or_else(|| {
+                    AppError::invalid(format!("Invalid index width in {{{}}}", token))
+                })?;
+            Token::Index { width: Some(width) }
+        }
+        ("start", None) => Token::Start,
+        ("end", None) => Token::End,
+        ("duration", None) => Token::Duration,
+        ("label", None) => Token::Label,
+        ("date", None) => Token::Date,
+        ("ext", None) => Token::Ext,
+        _ => {
+            return Err(AppError::invalid(format!(
+                "Unknown output template token {{{}}}; expected one of {}",
+                token,
+                TEMPLATE_TOKENS.join(", ")
+            )));
+        }
+    };
+    Ok(parsed)
+}
+
+/// Replace characters that are not allowed in file names on any supported OS.
+/// On Windows, device names such as `CON` or `LPT1` also get a `_` appended.
+pub fn sanitize_file_name(name: &str) -> String {
+    let cleaned = replace_invalid_chars(name);
+    avoid_reserved_name(cleaned.chars().take(MAX_NAME_CHARS).collect())
+}
+
+fn replace_invalid_chars(name: &str) -> String {
+    let cleaned = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect::<String>();
+    cleaned.trim().trim_matches('.').trim().to_string()
+}
+
+fn avoid_reserved_name(mut name: String) -> String {
+    if cfg!(windows) && is_reserved_windows_name(&name) {
+        name.push('_');
+    }
+    name
+}
+
+/// Windows refuses these names with any extension, e.g. `nul.mp4`.
+fn is_reserved_windows_name(name: &str) -> bool {
+    let device = name.split('.').next().unwrap_or_default().trim_end().to_uppercase();
+    match device.as_bytes() {
+        b"CON" | b"PRN" | b"AUX" | b"NUL" => true,
+        [b'C', b'O', b'M', digit] | [b'L', b'P', b'T', digit] => (b'1'..=b'9').contains(digit),
+        _ => false,
+    }
+}
+
+/// `HH-MM-SS`, with milliseconds when the time is not whole seconds.
+/// Colons are not allowed in Windows file names.
+fn format_time_token(seconds: Option<f64>) -> String {
+    let Some(seconds) = seconds else {
+        return String::new();
+    };
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    let (secs, millis) = (millis / 1000, millis % 1000);
+    let time = format!("{:02}-{:02}-{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+    if millis == 0 {
+        time
+    } else {
+        format!("{}.{:03}", time, millis)
+    }
+}
+
+/// Today's local date as `{date}` renders it.
+pub fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+/// Fail if two outputs would be written to the same file. File systems on
+/// Windows and macOS ignore case, so names are compared case-insensitively.
+pub fn check_name_collisions(paths: &[String]) -> Result<(), AppError> {
+    let mut seen = HashSet::new();
+    for path in paths {
+        if !seen.insert(path.to_lowercase()) {
+            return Err(AppError::invalid(format!(
+                "Several parts would be written to {}; add {{index}} to the output template",
+                path
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// What to do when an output file already exists.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OverwritePolicy {
+    /// Replace the existing file.
+    #[default]
+    Overwrite,
+    /// Keep the existing file and leave that output out.
+    Skip,
+    /// Write to the first free `{name}_1.{ext}`, `{name}_2.{ext}`, ...
+    Rename,
+    /// Refuse to start the split.
+    Fail,
+}
+
+/// The path each planned output is written to under `policy`, or `None` when
+/// it is skipped. Writing over `input_path` is refused whatever the policy.
+pub fn resolve_output_paths(
+    input_path: &str,
+    planned: &[String],
+    policy: OverwritePolicy,
+) -> Result<Vec<Option<String>>, AppError> {
+    // Renamed outputs must not take a name another output is planned under.
+    let mut claimed = planned
+        .iter()
+        .map(|path| path.to_lowercase())
+        .collect::<HashSet<_>>();
+    let mut resolved = Vec::with_capacity(planned.len());
+    for path in planned {
+        if is_same_file(Path::new(path), Path::new(input_path)) {
+            return Err(AppError::invalid(format!(
+                "Output {} would overwrite the input file",
+                path
+            )));
+        }
+        if !Path::new(path).exists() {
+            resolved.push(Some(path.clone()));
+            continue;
+        }
+        match policy {
+            OverwritePolicy::Overwrite => resolved.push(Some(path.clone())),
+            OverwritePolicy::Skip => resolved.push(None),
+            OverwritePolicy::Rename => {
+                let renamed = free_path(path, &claimed);
+                claimed.insert(renamed.to_lowercase());
+                resolved.push(Some(renamed));
+            }
+            OverwritePolicy::Fail => {
+                return Err(AppError::OutputExists {
+                    details: path.clone(),
+                });
+            }
+        }
+    }
+    Ok(resolved)
+}
+
+/// `path` with the first `_N` suffix that neither exists nor is `claimed`.
+fn free_path(path: &str, claimed: &HashSet<String>) -> String {
+    let path = Path::new(path);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("video");
+    let extension = path.extension().and_then(|s| s.to_str());
+    (1..)
+        .map(|n| {
+            let name = match extension {
+                Some(extension) => format!("{}_{}.{}", stem, n, extension),
+                None => format!("{}_{}", stem, n),
+            };
+            path.with_file_name(name).to_string_lossy().to_string()
+        })
+        .find(|candidate| {
+            !claimed.contains(&candidate.to_lowercase()) && !Path::new(candidate).exists()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether both paths name the same existing file, following symlinks and `..`.
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
@@ -0,0 +1,198 @@
+use crate::encode::{validate_encode_options, AudioCodec, EncodeOptions, VideoCodec};
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// Serializes read-modify-write cycles on the presets file.
+static PRESETS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Named `EncodeOptions`, either shipped with the app or saved by the user.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EncodePreset {
+    pub name: String,
+    pub encode: EncodeOptions,
+    /// Shipped with the app. Built-ins are never written to disk and cannot be
+    /// deleted or replaced.
+    #[serde(default)]
+    pub built_in: bool,
+}
+
+fn built_in_presets() -> Vec<EncodePreset> {
+    let preset = |name: &str, encode: EncodeOptions| EncodePreset {
+        name: name.to_string(),
+        encode,
+        built_in: true,
+    };
+    vec![
+        preset(
+            "WeChat 720p",
+            EncodeOptions {
+                video_codec: VideoCodec::X264,
+                crf: Some(23),
+                preset: Some("fast".to_string()),
+                pix_fmt: Some("yuv420p".to_string()),
+                max_height: Some(720),
+                audio_codec: AudioCodec::Aac,
+                audio_bitrate: Some("128k".to_string()),
+                ..Default::default()
+            },
+        ),
+        preset(
+            "YouTube 1080p",
+            EncodeOptions {
+                video_codec: VideoCodec::X264,
+                crf: Some(18),
+                preset: Some("medium".to_string()),
+                pix_fmt: Some("yuv420p".to_string()),
+                max_height: Some(1080),
+                audio_codec: AudioCodec::Aac,
+                audio_bitrate: Some("192k".to_string()),
+                ..Default::default()
+            },
+        ),
+        preset(
+            "Web VP9",
+            EncodeOptions {
+                video_codec: VideoCodec::Vp9,
+                crf: Some(31),
+                pix_fmt: Some("yuv420p".to_string()),
+                max_height: Some(1080),
+                audio_codec: AudioCodec::Opus,
+                audio_bitrate: Some("128k".to_string()),
+                ..Default::default()
+            },
+        ),
+        preset(
+            "Archive HEVC",
+            EncodeOptions {
+                video_codec: VideoCodec::X265,
+                crf: Some(20),
+                preset: Some("slow".to_string()),
+                audio_codec: AudioCodec::Copy,
+                ..Default::default()
+            },
+        ),
+    ]
+}
+
+fn presets_path(app_handle: &AppHandle) -> Result<PathBuf, AppError> {
+    let dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| AppError::internal(format!("Failed to resolve app config dir: {}", e)))?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| AppError::io("Failed to create app config dir", e))?;
+    Ok(dir.join("presets.json"))
+}
+
+fn read_user_presets(path: &PathBuf) -> Vec<EncodePreset> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str::<Vec<EncodePreset>>(&json).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|preset| EncodePreset {
+            built_in: false,
+            ..preset
+        })
+        .collect()
+}
+
+fn write_user_presets(path: &PathBuf, presets: &[EncodePreset]) -> Result<(), AppError> {
+    let json = serde_json::to_string_pretty(presets)
+        .map_err(|e| AppError::internal(format!("Failed to serialize presets: {}", e)))?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json).map_err(|e| AppError::io("Failed to write presets", e))?;
+    std::fs::rename(&tmp, path).map_err(|e| AppError::io("Failed to write presets", e))
+}
+
+fn is_built_in(name: &str) -> bool {
+    built_in_presets()
+        .iter()
+        .any(|preset| preset.name.eq_ignore_ascii_case(name))
+}
+
+/// Built-ins first, then the user's presets in the order they were saved.
+pub fn list_presets(app_handle: &AppHandle) -> Result<Vec<EncodePreset>, AppError> {
+    let path = presets_path(app_handle)?;
+    let _lock = PRESETS_LOCK
+        .lock()
+        .map_err(|_| AppError::internal("Failed to lock presets"))?;
+    let mut presets = built_in_presets();
+    presets.extend(
+        read_user_presets(&path)
+            .into_iter()
+            .filter(|preset| !is_built_in(&preset.name)),
+    );
+    Ok(presets)
+}
+
+/// Look a preset up by name, ignoring case.
+pub fn get_preset(app_handle: &AppHandle, name: &str) -> Result<EncodePreset, AppError> {
+    list_presets(app_handle)?
+        .into_iter()
+        .find(|preset| preset.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| AppError::not_found(format!("Preset {} not found", name)))
+}
+
+/// Create a user preset, or replace the one with the same name.
+pub async fn save_preset(
+    app_handle: &AppHandle,
+    name: &str,
+    encode: EncodeOptions,
+) -> Result<EncodePreset, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::invalid("Preset name must not be empty"));
+    }
+    if is_built_in(name) {
+        return Err(AppError::invalid(format!(
+            "{} is a built-in preset and cannot be replaced",
+            name
+        )));
+    }
+    validate_encode_options(app_handle, &encode).await?;
+
+    let preset = EncodePreset {
+        name: name.to_string(),
+        encode,
+        built_in: false,
+    };
+    let path = presets_path(app_handle)?;
+    let _lock = PRESETS_LOCK
+        .lock()
+        .map_err(|_| AppError::internal("Failed to lock presets"))?;
+    let mut presets = read_user_presets(&path);
+    match presets
+        .iter_mut()
+        .find(|existing| existing.name.eq_ignore_ascii_case(name))
+    {
+        Some(existing) => *existing = preset.clone(),
+        None => presets.push(preset.clone()),
+    }
+    write_user_presets(&path, &presets)?;
+    Ok(preset)
+}
+
+pub fn delete_preset(app_handle: &AppHandle, name: &str) -> Result<(), AppError> {
+    if is_built_in(name) {
+        return Err(AppError::invalid(format!(
+            "{} is a built-in preset and cannot be deleted",
+            name
+        )));
+    }
+
+    let path = presets_path(app_handle)?;
+    let _lock = PRESETS_LOCK
+        .lock()
+        .map_err(|_| AppError::internal("Failed to lock presets"))?;
+    let mut presets = read_user_presets(&path);
+    let before = presets.len();
+    presets.retain(|preset| !preset.name.eq_ignore_ascii_case(name));
+    if presets.len() == before {
+        return Err(AppError::not_found(format!("Preset {} not found", name)));
+    }
+    write_user_presets(&path, &presets)
+}
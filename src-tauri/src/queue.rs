@@ -0,0 +1,364 @@
+use crate::ffmpeg::{
+    attach_segment_thumbnails, cancel_split, default_range_workers, new_job_id, release_split,
+    reserve_split, split_video, split_video_by_chapters, split_video_by_count,
+    split_video_by_ranges, split_video_by_size, CutMode, OutputOptions, RangeSplitOptions,
+    SplitResult, ThumbnailMode, TimeRange,
+};
+use crate::encode::EncodeOptions;
+use crate::error::AppError;
+use crate::history;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How a queued job splits its input. Mirrors the `split_video_*` commands.
+/// `output` holds the container and file name template, flattened into the spec.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SplitSpec {
+    Duration {
+        segment_duration: u32,
+        #[serde(flatten)]
+        output: OutputOptions,
+    },
+    Count {
+        part_count: u32,
+        #[serde(flatten)]
+        output: OutputOptions,
+    },
+    Size {
+        max_bytes: u64,
+        #[serde(flatten)]
+        output: OutputOptions,
+    },
+    Ranges {
+        ranges: Vec<TimeRange>,
+        #[serde(default)]
+        mode: CutMode,
+        #[serde(default)]
+        max_workers: Option<usize>,
+        #[serde(default)]
+        encode: EncodeOptions,
+        #[serde(flatten)]
+        output: OutputOptions,
+    },
+    Chapters {
+        #[serde(default)]
+        mode: CutMode,
+        #[serde(default)]
+        encode: EncodeOptions,
+        #[serde(flatten)]
+        output: OutputOptions,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// Where a resumed range split picks up an interrupted run. Kept out of
+/// `SplitSpec` so history reruns always start from scratch.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RangeResume {
+    /// Index of the first range to cut; earlier outputs come from the interrupted run.
+    pub resume_from: usize,
+    /// Output path per range resolved by the interrupted run, `None` for ranges it skipped.
+    pub planned_outputs: Vec<Option<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QueuedJob {
+    pub id: String,
+    pub input_path: String,
+    pub output_dir: String,
+    pub spec: SplitSpec,
+    #[serde(default)]
+    pub thumbnails: Option<ThumbnailMode>,
+    /// Set for a range split resumed from a checkpoint.
+    #[serde(default)]
+    pub resume: Option<RangeResume>,
+    pub status: JobStatus,
+    pub result: Option<SplitResult>,
+    pub error: Option<AppError>,
+}
+
+struct QueueState {
+    jobs: Vec<QueuedJob>,
+    running: usize,
+    max_concurrent: usize,
+}
+
+/// Split jobs waiting to run, registered with `app.manage`.
+/// Jobs start in list order; at most `max_concurrent` run at once.
+pub struct JobQueue {
+    state: Mutex<QueueState>,
+}
+
+impl JobQueue {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                jobs: Vec::new(),
+                running: 0,
+                max_concurrent: max_concurrent.max(1),
+            }),
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, QueueState>, AppError> {
+        self.state
+            .lock()
+            .map_err(|_| AppError::internal("Failed to lock job queue"))
+    }
+}
+
+fn emit_job(app_handle: &AppHandle, job: &QueuedJob) {
+    let _ = app_handle.emit("queue-job-updated", job);
+}
+
+/// Start queued jobs until the concurrency limit is reached.
+fn pump(app_handle: &AppHandle) {
+    let queue = app_handle.state::<JobQueue>();
+    let Ok(mut state) = queue.lock() else {
+        return;
+    };
+
+    while state.running < state.max_concurrent {
+        let Some(job) = state
+            .jobs
+            .iter_mut()
+            .find(|job| job.status == JobStatus::Queued)
+        else {
+            break;
+        };
+        // Reserved under the queue lock so `remove_job` can cancel it before it registers.
+        if let Err(e) = reserve_split(&job.id) {
+            job.status = JobStatus::Failed;
+            job.error = Some(e);
+            emit_job(app_handle, job);
+            continue;
+        }
+        job.status = JobStatus::Running;
+        let job = job.clone();
+        state.running += 1;
+
+        emit_job(app_handle, &job);
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            run_job(app_handle, job).await;
+        });
+    }
+}
+
+/// Run a split described by `spec` under `job_id` and record it in the history.
+/// `resume` only applies to range splits.
+pub async fn run_split(
+    app_handle: &AppHandle,
+    job_id: &str,
+    input_path: &str,
+    output_dir: &str,
+    spec: &SplitSpec,
+    thumbnails: Option<ThumbnailMode>,
+    resume: Option<&RangeResume>,
+) -> Result<SplitResult, AppError> {
+    let started = Instant::now();
+    let (id, input, output) = (job_id, input_path, output_dir);
+    let outcome = match spec {
+        SplitSpec::Duration {
+            segment_duration,
+            output: options,
+        } => split_video(app_handle, id, input, output, *segment_duration, options).await,
+        SplitSpec::Count {
+            part_count,
+            output: options,
+        } => split_video_by_count(app_handle, id, input, output, *part_count, options).await,
+        SplitSpec::Size {
+            max_bytes,
+            output: options,
+        } => split_video_by_size(app_handle, id, input, output, *max_bytes, options).await,
+        SplitSpec::Ranges {
+            ranges,
+            mode,
+            max_workers,
+            encode,
+            output: output_options,
+        } => {
+            let options = RangeSplitOptions {
+                mode: *mode,
+                max_workers: max_workers.unwrap_or_else(default_range_workers),
+                resume_from: resume.map_or(0, |resume| resume.resume_from),
+                encode: encode.clone(),
+                output: output_options.clone(),
+                planned_outputs: resume.map(|resume| resume.planned_outputs.clone()),
+            };
+            split_video_by_ranges(app_handle, id, input, output, ranges.clone(), options).await
+        }
+        SplitSpec::Chapters {
+            mode,
+            encode,
+            output: output_options,
+        } => {
+            let (encode, options) = (encode.clone(), output_options.clone());
+            split_video_by_chapters(app_handle, id, input, output, *mode, encode, options).await
+        }
+    };
+    history::record_split(app_handle, id, input, output, spec, started, &outcome);
+
+    let mut result = outcome?;
+    if let Some(mode) = thumbnails {
+        attach_segment_thumbnails(app_handle, &mut result, mode).await;
+    }
+    Ok(result)
+}
+
+async fn run_job(app_handle: AppHandle, job: QueuedJob) {
+    let outcome = run_split(
+        &app_handle,
+        &job.id,
+        &job.input_path,
+        &job.output_dir,
+        &job.spec,
+        job.thumbnails,
+        job.resume.as_ref(),
+    )
+    .await;
+    release_split(&job.id);
+
+    let finished = {
+        let queue = app_handle.state::<JobQueue>();
+        let Ok(mut state) = queue.lock() else {
+            return;
+        };
+        state.running = state.running.saturating_sub(1);
+        // The job may have been removed while it was running.
+        state.jobs.iter_mut().find(|j| j.id == job.id).map(|entry| {
+            match outcome {
+                Ok(result) => {
+                    entry.status = JobStatus::Completed;
+                    entry.result = Some(result);
+                }
+                Err(AppError::Cancelled) => entry.status = JobStatus::Cancelled,
+                Err(e) => {
+                    entry.status = JobStatus::Failed;
+                    entry.error = Some(e);
+                }
+            }
+            entry.clone()
+        })
+    };
+
+    if let Some(job) = finished {
+        emit_job(&app_handle, &job);
+    }
+    pump(&app_handle);
+}
+
+/// Add a job to the end of the queue and start it if a worker is free.
+pub fn enqueue_job(
+    app_handle: &AppHandle,
+    input_path: String,
+    output_dir: String,
+    spec: SplitSpec,
+    thumbnails: Option<ThumbnailMode>,
+    resume: Option<RangeResume>,
+) -> Result<QueuedJob, AppError> {
+    let job = QueuedJob {
+        id: new_job_id(),
+        input_path,
+        output_dir,
+        spec,
+        thumbnails,
+        resume,
+        status: JobStatus::Queued,
+        result: None,
+        error: None,
+    };
+    {
+        let queue = app_handle.state::<JobQueue>();
+        let mut state = queue.lock()?;
+        state.jobs.push(job.clone());
+    }
+    emit_job(app_handle, &job);
+    pump(app_handle);
+    Ok(job)
+}
+
+pub fn list_jobs(app_handle: &AppHandle) -> Result<Vec<QueuedJob>, AppError> {
+    let queue = app_handle.state::<JobQueue>();
+    let state = queue.lock()?;
+    Ok(state.jobs.clone())
+}
+
+/// Whether the queue holds this job and has not finished it yet.
+pub fn is_job_pending(app_handle: &AppHandle, job_id: &str) -> bool {
+    let queue = app_handle.state::<JobQueue>();
+    let Ok(state) = queue.lock() else {
+        return false;
+    };
+    state.jobs.iter().any(|job| {
+        job.id == job_id && matches!(job.status, JobStatus::Queued | JobStatus::Running)
+    })
+}
+
+/// Drop a job from the queue. A running job is cancelled first.
+pub fn remove_job(app_handle: &AppHandle, job_id: &str) -> Result<bool, AppError> {
+    let removed = {
+        let queue = app_handle.state::<JobQueue>();
+        let mut state = queue.lock()?;
+        let Some(position) = state.jobs.iter().position(|job| job.id == job_id) else {
+            return Ok(false);
+        };
+        state.jobs.remove(position)
+    };
+    if removed.status == JobStatus::Running {
+        cancel_split(job_id)?;
+    }
+    Ok(true)
+}
+
+/// Reorder the queue to follow `job_ids`. Jobs not listed keep their
+/// relative order after the listed ones.
+pub fn reorder_jobs(
+    app_handle: &AppHandle,
+    job_ids: &[String],
+) -> Result<Vec<QueuedJob>, AppError> {
+    let queue = app_handle.state::<JobQueue>();
+    let mut state = queue.lock()?;
+
+    let rank: HashMap<&str, usize> = job_ids
+        .iter()
+        .enumerate()
+        .map(|(i, id)| (id.as_str(), i))
+        .collect();
+    if let Some(unknown) = job_ids
+        .iter()
+        .find(|id| !state.jobs.iter().any(|job| &job.id == *id))
+    {
+        return Err(AppError::not_found(format!("Unknown job id: {}", unknown)));
+    }
+
+    // Stable sort keeps unlisted jobs in their current order.
+    state
+        .jobs
+        .sort_by_key(|job| rank.get(job.id.as_str()).copied().unwrap_or(usize::MAX));
+    Ok(state.jobs.clone())
+}
+
+/// Change how many jobs may run at once. Takes effect immediately.
+pub fn set_max_concurrent(app_handle: &AppHandle, max_concurrent: usize) -> Result<(), AppError> {
+    {
+        let queue = app_handle.state::<JobQueue>();
+        let mut state = queue.lock()?;
+        state.max_concurrent = max_concurrent.max(1);
+    }
+    pump(app_handle);
+    Ok(())
+}
@@ -9,7 +9,12 @@ import ThemeToggle from './components/ThemeToggle';
 import VideoPlayer from './components/VideoPlayer';
 import SplitModeSelector from './components/SplitModeSelector';
 import TimeRangeEditor, { TimeRange } from './components/TimeRangeEditor';
-import { useVideoSplit } from './hooks/useVideoSplit';
+import AutoDetectPanel from './components/AutoDetectPanel';
+import JobQueuePanel from './components/JobQueuePanel';
+import UnfinishedJobsBanner from './components/UnfinishedJobsBanner';
+import EncodeSettings from './components/EncodeSettings';
+import { useVideoSplit, CutMode, Container, EncodeOptions, OverwritePolicy, ThumbnailMode, DEFAULT_ENCODE_OPTIONS } from './hooks/useVideoSplit';
+import { useJobQueue, SplitSpec } from './hooks/useJobQueue';
 import './index.css';
 
 interface FFmpegStatus {
@@ -31,6 +36,12 @@ function App() {
   const [isCheckingFfmpeg, setIsCheckingFfmpeg] = useState(false);
   const [splitMode, setSplitMode] = useState<'interval' | 'ranges'>('ranges');
   const [timeRanges, setTimeRanges] = useState<TimeRange[]>([]);
+  const [cutMode, setCutMode] = useState<CutMode>('reencode');
+  const [encodeOptions, setEncodeOptions] = useState<EncodeOptions>(DEFAULT_ENCODE_OPTIONS);
+  const [container, setContainer] = useState<Container | null>(null);
+  const [nameTemplate, setNameTemplate] = useState('');
+  const [overwrite, setOverwrite] = useState<OverwritePolicy>('rename');
+  const [thumbnails, setThumbnails] = useState<ThumbnailMode | null>(null);
 
   const {
     videoInfo,
@@ -42,8 +53,14 @@ function App() {
     loadVideoInfo,
     splitVideo,
     splitVideoByRanges,
+    splitVideoByChapters,
+    detectScenes,
+    detectSilence,
+    cancelSplit,
   } = useVideoSplit();
 
+  const { jobs, enqueueJob, removeJob, reorderJobs } = useJobQueue();
+
   // Set default output directory to same as input file
   useEffect(() => {
     if (selectedFile && !outputDir) {
@@ -75,16 +92,69 @@ function App() {
     setTimeRanges(nextRanges);
   };
 
+  const handleDetectScenes = async (threshold: number, minSegmentSeconds: number) => {
+    if (!selectedFile) return;
+
+    const detected = await detectScenes(selectedFile, threshold, minSegmentSeconds);
+    if (detected) {
+      setTimeRanges(detected);
+    }
+  };
+
+  const handleDetectSilence = async (noiseDb: number, minSilenceSeconds: number) => {
+    if (!selectedFile) return;
+
+    const detected = await detectSilence(selectedFile, noiseDb, minSilenceSeconds);
+    if (detected) {
+      setTimeRanges(detected);
+    }
+  };
+
+  // An empty template keeps the default {stem}_000 naming
+  const template = nameTemplate.trim() || null;
+
   const handleSplit = async () => {
     if (!selectedFile || !outputDir) return;
 
     if (splitMode === 'interval') {
-      await splitVideo(selectedFile, outputDir, segmentDuration);
+      await splitVideo(selectedFile, outputDir, segmentDuration, thumbnails, container, template, overwrite);
     } else {
-      await splitVideoByRanges(selectedFile, outputDir, timeRanges);
+      await splitVideoByRanges(selectedFile, outputDir, timeRanges, cutMode, thumbnails, encodeOptions, container, template, overwrite);
     }
   };
 
+  const currentSpec = (): SplitSpec => splitMode === 'interval'
+    ? { kind: 'duration', segment_duration: segmentDuration, container, name_template: template, overwrite }
+    : {
+      kind: 'ranges',
+      ranges: timeRanges.map(r => ({ start_seconds: r.startTime, end_seconds: r.endTime })),
+      mode: cutMode,
+      encode: encodeOptions,
+      container,
+      name_template: template,
+      overwrite,
+    };
+
+  const handleEnqueue = async () => {
+    if (!selectedFile || !outputDir) return;
+
+    await enqueueJob(selectedFile, outputDir, currentSpec(), thumbnails);
+  };
+
+  // Dropped batches are queued as interval splits with the current duration
+  const handleMultipleFiles = async (paths: string[]) => {
+    for (const path of paths) {
+      const dir = outputDir || path.substring(0, path.lastIndexOf('/'));
+      await enqueueJob(path, dir, { kind: 'duration', segment_duration: segmentDuration, container, name_template: template, overwrite }, thumbnails);
+    }
+  };
+
+  const handleSplitByChapters = async () => {
+    if (!selectedFile || !outputDir) return;
+
+    await splitVideoByChapters(selectedFile, outputDir, cutMode, encodeOptions, container, template, overwrite);
+  };
+
   const handleCheckFfmpeg = async () => {
     setIsCheckingFfmpeg(true);
     try {
@@ -201,9 +271,12 @@ function App() {
 
       {/* Main Content */}
       <main className="flex-1 max-w-2xl mx-auto w-full space-y-6">
+        <UnfinishedJobsBanner />
+
         {/* File Drop Zone */}
         <FileDropZone
           onFileSelect={handleFileSelect}
+          onMultipleFiles={handleMultipleFiles}
           selectedFile={selectedFile}
           videoInfo={videoInfo}
           disabled={isProcessing}
@@ -254,16 +327,54 @@ function App() {
               />
             ) : (
               selectedFile && (
-                <TimeRangeEditor
-                  filePath={selectedFile}
-                  duration={videoInfo.duration}
-                  ranges={timeRanges}
-                  onAddRange={handleAddRange}
-                  onSetRanges={handleSetRanges}
-                  onUpdateRange={handleUpdateRange}
-                  onDeleteRange={handleDeleteRange}
-                  disabled={isProcessing}
-                />
+                <>
+                  <AutoDetectPanel
+                    onDetectScenes={handleDetectScenes}
+                    onDetectSilence={handleDetectSilence}
+                    disabled={isProcessing}
+                  />
+                  {videoInfo.chapters.length > 0 && (
+                    <button
+                      onClick={handleSplitByChapters}
+                      disabled={isProcessing || !outputDir}
+                      className="w-full py-2 rounded-lg glass text-sm font-medium text-slate-600 dark:text-slate-300 hover:bg-slate-200 dark:hover:bg-slate-700 transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                    >
+                      📑 按章节切分（共 {videoInfo.chapters.length} 章）
+                    </button>
+                  )}
+                  <TimeRangeEditor
+                    filePath={selectedFile}
+                    duration={videoInfo.duration}
+                    ranges={timeRanges}
+                    onAddRange={handleAddRange}
+                    onSetRanges={handleSetRanges}
+                    onUpdateRange={handleUpdateRange}
+                    onDeleteRange={handleDeleteRange}
+                    disabled={isProcessing}
+                  />
+                  <div className="flex items-center space-x-4">
+                    <label className="text-sm font-medium text-slate-600 dark:text-slate-300 w-24">
+                      🎞️ 切分方式
+                    </label>
+                    <select
+                      value={cutMode}
+                      onChange={(e) => setCutMode(e.target.value as CutMode)}
+                      disabled={isProcessing}
+                      className="flex-1 px-3 py-2 rounded-lg bg-slate-50 dark:bg-slate-800/50 border border-slate-200 dark:border-slate-600 text-sm text-slate-700 dark:text-slate-300"
+                    >
+                      <option value="reencode">精确（重新编码）</option>
+                      <option value="copy">无损（对齐关键帧）</option>
+                      <option value="smart">智能（仅重新编码切点附近）</option>
+                    </select>
+                  </div>
+                  {cutMode !== 'copy' && (
+                    <EncodeSettings
+                      value={encodeOptions}
+                      onChange={setEncodeOptions}
+                      disabled={isProcessing}
+                    />
+                  )}
+                </>
               )
             )}
 
@@ -272,6 +383,73 @@ function App() {
               onChange={setOutputDir}
               disabled={isProcessing}
             />
+
+            <div className="flex items-center space-x-4">
+              <label className="text-sm font-medium text-slate-600 dark:text-slate-300 w-24">
+                📦 输出格式
+              </label>
+              <select
+                value={container ?? ''}
+                onChange={(e) => setContainer(e.target.value ? e.target.value as Container : null)}
+                disabled={isProcessing}
+                className="flex-1 px-3 py-2 rounded-lg bg-slate-50 dark:bg-slate-800/50 border border-slate-200 dark:border-slate-600 text-sm text-slate-700 dark:text-slate-300"
+              >
+                <option value="">与源文件相同</option>
+                <option value="mp4">MP4</option>
+                <option value="mkv">MKV</option>
+                <option value="mov">MOV</option>
+                <option value="webm">WebM</option>
+                <option value="ts">TS</option>
+              </select>
+            </div>
+
+            <div className="flex items-center space-x-4">
+              <label className="text-sm font-medium text-slate-600 dark:text-slate-300 w-24">
+                🏷️ 文件命名
+              </label>
+              <input
+                type="text"
+                placeholder="{stem}_{index:04}"
+                value={nameTemplate}
+                onChange={(e) => setNameTemplate(e.target.value)}
+                disabled={isProcessing}
+                className="flex-1 px-3 py-2 rounded-lg bg-slate-50 dark:bg-slate-800/50 border border-slate-200 dark:border-slate-600 text-sm text-slate-700 dark:text-slate-300"
+                title="可用变量：{stem} {index} {index:04} {start} {end} {duration} {label} {date} {ext}，留空使用默认命名"
+              />
+            </div>
+
+            <div className="flex items-center space-x-4">
+              <label className="text-sm font-medium text-slate-600 dark:text-slate-300 w-24">
+                📄 同名文件
+              </label>
+              <select
+                value={overwrite}
+                onChange={(e) => setOverwrite(e.target.value as OverwritePolicy)}
+                disabled={isProcessing}
+                className="flex-1 px-3 py-2 rounded-lg bg-slate-50 dark:bg-slate-800/50 border border-slate-200 dark:border-slate-600 text-sm text-slate-700 dark:text-slate-300"
+              >
+                <option value="rename">自动重命名</option>
+                <option value="skip">跳过</option>
+                <option value="overwrite">覆盖</option>
+                <option value="fail">报错停止</option>
+              </select>
+            </div>
+
+            <div className="flex items-center space-x-4">
+              <label className="text-sm font-medium text-slate-600 dark:text-slate-300 w-24">
+                🖼️ 缩略图
+              </label>
+              <select
+                value={thumbnails ?? ''}
+                onChange={(e) => setThumbnails(e.target.value ? e.target.value as ThumbnailMode : null)}
+                disabled={isProcessing}
+                className="flex-1 px-3 py-2 rounded-lg bg-slate-50 dark:bg-slate-800/50 border border-slate-200 dark:border-slate-600 text-sm text-slate-700 dark:text-slate-300"
+              >
+                <option value="">不生成</option>
+                <option value="midpoint">片段中间帧</option>
+                <option value="interesting">自动挑选画面</option>
+              </select>
+            </div>
           </div>
         )}
 
@@ -281,11 +459,22 @@ function App() {
             progress={progress?.percentage || 0}
             currentSegment={progress?.current_segment || 0}
             totalSegments={progress?.total_segments || 0}
-            currentFile={progress?.current_file || ''}
+            currentFile={progress?.output_file || progress?.current_file || ''}
             isProcessing={isProcessing}
+            speed={progress?.speed}
+            etaSeconds={progress?.eta_seconds}
           />
         )}
 
+        {isProcessing && (
+          <button
+            onClick={cancelSplit}
+            className="w-full py-2 rounded-lg glass text-sm font-medium text-red-500 dark:text-red-400 hover:bg-red-500/10 transition-colors"
+          >
+            取消切分
+          </button>
+        )}
+
         {/* Error */}
         {error && (
           <div className="glass rounded-xl p-4 border border-red-500/30 bg-red-900/10 dark:bg-red-900/10">
@@ -299,6 +488,10 @@ function App() {
         {result?.success && (
           <ResultList
             files={result.output_files}
+            ranges={result.output_ranges}
+            thumbnails={result.thumbnails}
+            conversions={result.conversions}
+            skippedFiles={result.skipped_files}
           />
         )}
 
@@ -340,6 +533,17 @@ function App() {
           )}
         </button>
 
+        {canSplit && (
+          <button
+            onClick={handleEnqueue}
+            className="w-full py-2 rounded-xl text-sm font-medium bg-slate-200 dark:bg-slate-700 text-slate-600 dark:text-slate-300 hover:bg-slate-300 dark:hover:bg-slate-600 transition-colors"
+          >
+            ➕ 加入后台队列
+          </button>
+        )}
+
+        <JobQueuePanel jobs={jobs} onRemove={removeJob} onReorder={reorderJobs} />
+
         {/* Info */}
         {videoInfo && !isProcessing && !result && (
           <p className="text-center text-sm text-slate-500 dark:text-slate-500">
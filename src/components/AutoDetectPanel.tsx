@@ -0,0 +1,100 @@
+import { useState } from 'react';
+
+interface AutoDetectPanelProps {
+    onDetectScenes: (threshold: number, minSegmentSeconds: number) => void;
+    onDetectSilence: (noiseDb: number, minSilenceSeconds: number) => void;
+    disabled?: boolean;
+}
+
+const inputClass = 'w-16 px-2 py-1 rounded-lg bg-slate-50 dark:bg-slate-800/50 border border-slate-200 dark:border-slate-600 text-slate-700 dark:text-slate-300';
+
+const buttonClass = (disabled: boolean) => `
+    px-3 py-1.5 rounded-lg
+    bg-slate-100 dark:bg-slate-700
+    text-slate-700 dark:text-slate-300
+    hover:bg-slate-200 dark:hover:bg-slate-600
+    transition-colors font-medium
+    ${disabled ? 'opacity-50 cursor-not-allowed' : ''}
+`;
+
+const AutoDetectPanel = ({ onDetectScenes, onDetectSilence, disabled = false }: AutoDetectPanelProps) => {
+    const [threshold, setThreshold] = useState(0.3);
+    const [minSegmentSeconds, setMinSegmentSeconds] = useState(2);
+    const [noiseDb, setNoiseDb] = useState(-30);
+    const [minSilenceSeconds, setMinSilenceSeconds] = useState(1);
+
+    return (
+        <div className="space-y-2 text-sm">
+            <span className="font-medium text-slate-600 dark:text-slate-300">自动检测:</span>
+            <div className="flex flex-wrap items-center gap-3">
+                <label className="flex items-center gap-1 text-slate-500 dark:text-slate-400">
+                    灵敏度
+                    <input
+                        type="number"
+                        min={0.05}
+                        max={1}
+                        step={0.05}
+                        value={threshold}
+                        onChange={(e) => setThreshold(Number(e.target.value))}
+                        disabled={disabled}
+                        className={inputClass}
+                    />
+                </label>
+                <label className="flex items-center gap-1 text-slate-500 dark:text-slate-400">
+                    最短片段(秒)
+                    <input
+                        type="number"
+                        min={0}
+                        step={1}
+                        value={minSegmentSeconds}
+                        onChange={(e) => setMinSegmentSeconds(Number(e.target.value))}
+                        disabled={disabled}
+                        className={inputClass}
+                    />
+                </label>
+                <button
+                    onClick={() => onDetectScenes(threshold, minSegmentSeconds)}
+                    disabled={disabled}
+                    className={buttonClass(disabled)}
+                >
+                    🎬 镜头切换
+                </button>
+            </div>
+            <div className="flex flex-wrap items-center gap-3">
+                <label className="flex items-center gap-1 text-slate-500 dark:text-slate-400">
+                    噪声阈值(dB)
+                    <input
+                        type="number"
+                        max={0}
+                        step={1}
+                        value={noiseDb}
+                        onChange={(e) => setNoiseDb(Number(e.target.value))}
+                        disabled={disabled}
+                        className={inputClass}
+                    />
+                </label>
+                <label className="flex items-center gap-1 text-slate-500 dark:text-slate-400">
+                    最短静音(秒)
+                    <input
+                        type="number"
+                        min={0.1}
+                        step={0.1}
+                        value={minSilenceSeconds}
+                        onChange={(e) => setMinSilenceSeconds(Number(e.target.value))}
+                        disabled={disabled}
+                        className={inputClass}
+                    />
+                </label>
+                <button
+                    onClick={() => onDetectSilence(noiseDb, minSilenceSeconds)}
+                    disabled={disabled}
+                    className={buttonClass(disabled)}
+                >
+                    🔇 静音停顿
+                </button>
+            </div>
+        </div>
+    );
+};
+
+export default AutoDetectPanel;
@@ -0,0 +1,213 @@
+import { useState, useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { AudioCodec, EncodeOptions, EncodePreset, VideoCodec } from '../hooks/useVideoSplit';
+import { formatError } from '../utils/appError';
+
+interface EncoderSupport {
+    video: VideoCodec[];
+    audio: AudioCodec[];
+}
+
+interface EncodeSettingsProps {
+    value: EncodeOptions;
+    onChange: (options: EncodeOptions) => void;
+    disabled?: boolean;
+}
+
+const VIDEO_CODECS: { value: VideoCodec; label: string }[] = [
+    { value: 'x264', label: 'H.264 (x264)' },
+    { value: 'x265', label: 'HEVC (x265)' },
+    { value: 'vp9', label: 'VP9' },
+    { value: 'av1', label: 'AV1 (SVT-AV1)' },
+    { value: 'prores', label: 'ProRes' },
+];
+
+const AUDIO_CODECS: { value: AudioCodec; label: string }[] = [
+    { value: 'aac', label: 'AAC' },
+    { value: 'opus', label: 'Opus' },
+    { value: 'mp3', label: 'MP3' },
+    { value: 'flac', label: 'FLAC' },
+    { value: 'pcm', label: 'PCM' },
+    { value: 'copy', label: '保留原音频' },
+];
+
+const selectClass = 'flex-1 px-3 py-2 rounded-lg bg-slate-50 dark:bg-slate-800/50 border border-slate-200 dark:border-slate-600 text-sm text-slate-700 dark:text-slate-300';
+
+const EncodeSettings = ({ value, onChange, disabled = false }: EncodeSettingsProps) => {
+    const [support, setSupport] = useState<EncoderSupport | null>(null);
+    const [presets, setPresets] = useState<EncodePreset[]>([]);
+    const [presetName, setPresetName] = useState('');
+    const [presetError, setPresetError] = useState<string | null>(null);
+
+    const loadPresets = useCallback(async () => {
+        try {
+            setPresets(await invoke<EncodePreset[]>('list_presets'));
+        } catch (err) {
+            setPresetError(formatError(err));
+        }
+    }, []);
+
+    useEffect(() => {
+        invoke<EncoderSupport>('get_encoder_support')
+            .then(setSupport)
+            .catch(() => setSupport(null));
+        loadPresets();
+    }, [loadPresets]);
+
+    const selectedPreset = presets.find(p => p.name === presetName);
+
+    const handlePresetSelect = (name: string) => {
+        setPresetName(name);
+        setPresetError(null);
+        const preset = presets.find(p => p.name === name);
+        if (preset) {
+            onChange({ ...preset.encode });
+        }
+    };
+
+    const handleSavePreset = async () => {
+        const name = presetName.trim();
+        if (!name) return;
+        setPresetError(null);
+        try {
+            await invoke<EncodePreset>('save_preset', { name, encode: value });
+            await loadPresets();
+        } catch (err) {
+            setPresetError(formatError(err));
+        }
+    };
+
+    const handleDeletePreset = async () => {
+        if (!selectedPreset || selectedPreset.built_in) return;
+        setPresetError(null);
+        try {
+            await invoke('delete_preset', { name: selectedPreset.name });
+            setPresetName('');
+            await loadPresets();
+        } catch (err) {
+            setPresetError(formatError(err));
+        }
+    };
+
+    const isAvailable = (codec: VideoCodec | AudioCodec, list?: string[]) =>
+        !list || list.includes(codec);
+
+    // Presets and CRF scales differ per encoder, so they reset with it
+    const handleVideoCodecChange = (videoCodec: VideoCodec) => {
+        onChange({ ...value, video_codec: videoCodec, crf: null, preset: null });
+    };
+
+    return (
+        <div className="space-y-2">
+            <div className="flex items-center space-x-4">
+                <label className="text-sm font-medium text-slate-600 dark:text-slate-300 w-24">
+                    ⭐ 编码预设
+                </label>
+                <select
+                    value={selectedPreset ? presetName : ''}
+                    onChange={(e) => handlePresetSelect(e.target.value)}
+                    disabled={disabled}
+                    className={selectClass}
+                >
+                    <option value="">自定义</option>
+                    {presets.map(preset => (
+                        <option key={preset.name} value={preset.name}>
+                            {preset.built_in ? preset.name : `${preset.name}（我的）`}
+                        </option>
+                    ))}
+                </select>
+                {selectedPreset && !selectedPreset.built_in && (
+                    <button
+                        onClick={handleDeletePreset}
+                        disabled={disabled}
+                        className="text-xs text-red-500 hover:text-red-400 disabled:opacity-50"
+                        title="删除预设"
+                    >
+                        ✕
+                    </button>
+                )}
+            </div>
+            <div className="flex items-center space-x-4">
+                <span className="w-24" />
+                <input
+                    type="text"
+                    placeholder="预设名称"
+                    value={presetName}
+                    onChange={(e) => setPresetName(e.target.value)}
+                    disabled={disabled}
+                    className="flex-1 px-3 py-2 rounded-lg bg-slate-50 dark:bg-slate-800/50 border border-slate-200 dark:border-slate-600 text-sm text-slate-700 dark:text-slate-300"
+                />
+                <button
+                    onClick={handleSavePreset}
+                    disabled={disabled || !presetName.trim() || selectedPreset?.built_in}
+                    className="px-3 py-2 rounded-lg bg-slate-100 dark:bg-slate-700 text-sm text-slate-700 dark:text-slate-300 hover:bg-slate-200 dark:hover:bg-slate-600 transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                >
+                    💾 保存预设
+                </button>
+            </div>
+            {presetError && (
+                <p className="text-xs text-red-500">{presetError}</p>
+            )}
+            <div className="flex items-center space-x-4">
+                <label className="text-sm font-medium text-slate-600 dark:text-slate-300 w-24">
+                    🎬 视频编码
+                </label>
+                <select
+                    value={value.video_codec}
+                    onChange={(e) => handleVideoCodecChange(e.target.value as VideoCodec)}
+                    disabled={disabled}
+                    className={selectClass}
+                >
+                    {VIDEO_CODECS.map(codec => (
+                        <option
+                            key={codec.value}
+                            value={codec.value}
+                            disabled={!isAvailable(codec.value, support?.video)}
+                        >
+                            {codec.label}
+                        </option>
+                    ))}
+                </select>
+                {value.video_codec !== 'prores' && (
+                    <input
+                        type="number"
+                        min={0}
+                        max={value.video_codec === 'x264' || value.video_codec === 'x265' ? 51 : 63}
+                        placeholder="CRF"
+                        value={value.crf ?? ''}
+                        onChange={(e) => onChange({
+                            ...value,
+                            crf: e.target.value === '' ? null : Number(e.target.value),
+                        })}
+                        disabled={disabled}
+                        className="w-20 px-3 py-2 rounded-lg bg-slate-50 dark:bg-slate-800/50 border border-slate-200 dark:border-slate-600 text-sm text-slate-700 dark:text-slate-300"
+                        title="质量（CRF），留空使用默认值"
+                    />
+                )}
+            </div>
+            <div className="flex items-center space-x-4">
+                <label className="text-sm font-medium text-slate-600 dark:text-slate-300 w-24">
+                    🔊 音频编码
+                </label>
+                <select
+                    value={value.audio_codec}
+                    onChange={(e) => onChange({ ...value, audio_codec: e.target.value as AudioCodec })}
+                    disabled={disabled}
+                    className={selectClass}
+                >
+                    {AUDIO_CODECS.map(codec => (
+                        <option
+                            key={codec.value}
+                            value={codec.value}
+                            disabled={!isAvailable(codec.value, support?.audio)}
+                        >
+                            {codec.label}
+                        </option>
+                    ))}
+                </select>
+            </div>
+        </div>
+    );
+};
+
+export default EncodeSettings;
@@ -4,11 +4,21 @@ import { listen } from '@tauri-apps/api/event';
 
 interface FileDropZoneProps {
     onFileSelect: (path: string) => void;
+    onMultipleFiles?: (paths: string[]) => void;
     selectedFile: string | null;
     videoInfo: {
         filename: string;
         duration_formatted: string;
         duration: number;
+        video: {
+            codec: string;
+            width: number;
+            height: number;
+            frame_rate: number | null;
+            is_vfr: boolean;
+            rotation: number;
+        } | null;
+        audio_tracks: unknown[];
     } | null;
     disabled?: boolean;
 }
@@ -20,6 +30,7 @@ interface DragDropPayload {
 
 const FileDropZone = ({
     onFileSelect,
+    onMultipleFiles,
     selectedFile,
     videoInfo,
     disabled = false,
@@ -32,15 +43,17 @@ const FileDropZone = ({
             if (disabled) return;
 
             const paths = event.payload.paths;
-            if (paths && paths.length > 0) {
-                // Get the first file and check if it's a video
-                const filePath = paths[0];
-                const ext = filePath.split('.').pop()?.toLowerCase();
-                const videoExts = ['mp4', 'mkv', 'avi', 'mov', 'webm', 'flv', 'wmv', 'm4v'];
+            const videoExts = ['mp4', 'mkv', 'avi', 'mov', 'webm', 'flv', 'wmv', 'm4v'];
+            const videoPaths = (paths || []).filter(p => {
+                const ext = p.split('.').pop()?.toLowerCase();
+                return !!ext && videoExts.includes(ext);
+            });
 
-                if (ext && videoExts.includes(ext)) {
-                    onFileSelect(filePath);
-                }
+            // Several videos at once go to the batch queue when supported
+            if (videoPaths.length > 1 && onMultipleFiles) {
+                onMultipleFiles(videoPaths);
+            } else if (videoPaths.length > 0) {
+                onFileSelect(videoPaths[0]);
             }
             setIsDragging(false);
         });
@@ -60,7 +73,7 @@ const FileDropZone = ({
             unlistenEnter.then(fn => fn());
             unlistenLeave.then(fn => fn());
         };
-    }, [disabled, onFileSelect]);
+    }, [disabled, onFileSelect, onMultipleFiles]);
 
     const handleClick = async () => {
         if (disabled) return;
@@ -163,6 +176,21 @@ const FileDropZone = ({
                                         时长: {videoInfo.duration_formatted}
                                     </p>
                                 )}
+                                {videoInfo?.video && (
+                                    <p className="text-xs text-slate-500 dark:text-slate-400 mt-1">
+                                        {videoInfo.video.codec.toUpperCase()} · {videoInfo.video.width}×{videoInfo.video.height}
+                                        {videoInfo.video.frame_rate && ` · ${videoInfo.video.frame_rate.toFixed(2)} fps`}
+                                        {videoInfo.audio_tracks.length > 0 && ` · ${videoInfo.audio_tracks.length} 条音轨`}
+                                    </p>
+                                )}
+                                {videoInfo?.video && (videoInfo.video.is_vfr || videoInfo.video.rotation !== 0) && (
+                                    <p className="text-xs text-amber-600 dark:text-amber-400 mt-1">
+                                        ⚠️ {[
+                                            videoInfo.video.is_vfr && '可变帧率',
+                                            videoInfo.video.rotation !== 0 && `旋转 ${videoInfo.video.rotation}°`,
+                                        ].filter(Boolean).join('，')}
+                                    </p>
+                                )}
                             </div>
                             <p className="text-xs text-slate-400 dark:text-slate-500">
                                 点击可重新选择文件
@@ -0,0 +1,81 @@
+import type { QueuedJob, JobStatus } from '../hooks/useJobQueue';
+import { formatError } from '../utils/appError';
+
+interface JobQueuePanelProps {
+    jobs: QueuedJob[];
+    onRemove: (jobId: string) => void;
+    onReorder: (jobIds: string[]) => void;
+}
+
+const STATUS_LABELS: Record<JobStatus, string> = {
+    queued: '等待中',
+    running: '处理中',
+    completed: '已完成',
+    failed: '失败',
+    cancelled: '已取消',
+};
+
+const STATUS_COLORS: Record<JobStatus, string> = {
+    queued: 'text-slate-500 dark:text-slate-400',
+    running: 'text-primary-600 dark:text-primary-400',
+    completed: 'text-green-600 dark:text-green-400',
+    failed: 'text-red-500 dark:text-red-400',
+    cancelled: 'text-amber-600 dark:text-amber-400',
+};
+
+const JobQueuePanel = ({ jobs, onRemove, onReorder }: JobQueuePanelProps) => {
+    if (jobs.length === 0) return null;
+
+    const getFileName = (path: string) => path.split('/').pop() || path;
+
+    const moveUp = (index: number) => {
+        if (index === 0) return;
+        const ids = jobs.map(j => j.id);
+        [ids[index - 1], ids[index]] = [ids[index], ids[index - 1]];
+        onReorder(ids);
+    };
+
+    return (
+        <div className="glass rounded-xl p-4 space-y-2">
+            <h3 className="text-sm font-medium text-slate-700 dark:text-slate-200">
+                任务队列 ({jobs.length})
+            </h3>
+            <div className="max-h-48 overflow-y-auto space-y-1">
+                {jobs.map((job, index) => (
+                    <div
+                        key={job.id}
+                        className="flex items-center justify-between gap-2 text-sm py-2 px-3 rounded-lg hover:bg-slate-100 dark:hover:bg-slate-700/30"
+                    >
+                        <span className="truncate text-slate-600 dark:text-slate-300" title={job.error ? formatError(job.error) : job.input_path}>
+                            {getFileName(job.input_path)}
+                        </span>
+                        <div className="flex items-center gap-2 flex-shrink-0">
+                            <span className={`text-xs ${STATUS_COLORS[job.status]}`}>
+                                {STATUS_LABELS[job.status]}
+                                {job.status === 'completed' && job.result && ` · ${job.result.output_files.length} 个文件`}
+                            </span>
+                            {job.status === 'queued' && index > 0 && (
+                                <button
+                                    onClick={() => moveUp(index)}
+                                    className="text-xs text-slate-500 hover:text-slate-700 dark:hover:text-slate-300"
+                                    title="上移"
+                                >
+                                    ↑
+                                </button>
+                            )}
+                            <button
+                                onClick={() => onRemove(job.id)}
+                                className="text-xs text-red-500 hover:text-red-400"
+                                title={job.status === 'running' ? '取消并移除' : '移除'}
+                            >
+                                ✕
+                            </button>
+                        </div>
+                    </div>
+                ))}
+            </div>
+        </div>
+    );
+};
+
+export default JobQueuePanel;
@@ -4,14 +4,25 @@ interface ProgressBarProps {
     totalSegments: number;
     currentFile: string;
     isProcessing: boolean;
+    speed?: number | null;
+    etaSeconds?: number | null;
 }
 
+const formatEta = (seconds: number) => {
+    const total = Math.max(0, Math.round(seconds));
+    const m = Math.floor(total / 60);
+    const s = total % 60;
+    return m > 0 ? `${m} 分 ${s} 秒` : `${s} 秒`;
+};
+
 const ProgressBar = ({
     progress,
     currentSegment,
     totalSegments,
     currentFile,
     isProcessing,
+    speed,
+    etaSeconds,
 }: ProgressBarProps) => {
     if (!isProcessing && progress === 0) return null;
 
@@ -44,6 +55,13 @@ const ProgressBar = ({
                     {currentFile}
                 </span>
             </div>
+
+            {isProcessing && (speed != null || etaSeconds != null) && (
+                <div className="flex items-center justify-between text-xs text-slate-500 dark:text-slate-400">
+                    <span>{speed != null ? `速度 ${speed.toFixed(1)}x` : ''}</span>
+                    <span>{etaSeconds != null ? `剩余约 ${formatEta(etaSeconds)}` : ''}</span>
+                </div>
+            )}
         </div>
     );
 };
@@ -1,13 +1,43 @@
 import { useState, useEffect } from 'react';
 import { revealItemInDir } from '@tauri-apps/plugin-opener';
-import { invoke } from '@tauri-apps/api/core';
+import { convertFileSrc, invoke } from '@tauri-apps/api/core';
 import VideoPlayer from './VideoPlayer';
+import { formatError } from '../utils/appError';
+import type { StreamConversion } from '../hooks/useVideoSplit';
+
+interface OutputRange {
+    start_seconds: number;
+    end_seconds: number;
+}
 
 interface ResultListProps {
     files: string[];
+    ranges?: OutputRange[];
+    thumbnails?: (string | null)[];
+    conversions?: StreamConversion[];
+    skippedFiles?: string[];
 }
 
-const ResultList = ({ files }: ResultListProps) => {
+const STREAM_KIND_LABELS: Record<StreamConversion['kind'], string> = {
+    video: '视频',
+    audio: '音频',
+    subtitle: '字幕',
+};
+
+const describeConversion = (conversion: StreamConversion) => {
+    const stream = `${STREAM_KIND_LABELS[conversion.kind]}（${conversion.codec}）`;
+    return conversion.target
+        ? `${stream} 不被目标容器支持，已转码为 ${conversion.target}`
+        : `${stream} 不被目标容器支持，已丢弃`;
+};
+
+const formatSeconds = (seconds: number) => {
+    const mins = Math.floor(seconds / 60);
+    const secs = (seconds % 60).toFixed(2).padStart(5, '0');
+    return `${mins}:${secs}`;
+};
+
+const ResultList = ({ files, ranges = [], thumbnails = [], conversions = [], skippedFiles = [] }: ResultListProps) => {
     const [previewFile, setPreviewFile] = useState<string | null>(null);
     const [previewDuration, setPreviewDuration] = useState<number | null>(null);
     const [previewError, setPreviewError] = useState<string | null>(null);
@@ -51,7 +81,7 @@ const ResultList = ({ files }: ResultListProps) => {
             })
             .catch((err) => {
                 if (!cancelled) {
-                    setPreviewError(String(err));
+                    setPreviewError(formatError(err));
                 }
             });
 
@@ -81,6 +111,25 @@ const ResultList = ({ files }: ResultListProps) => {
                     </button>
                 </div>
 
+                {conversions.length > 0 && (
+                    <ul className="mb-3 space-y-1 text-xs text-amber-600 dark:text-amber-400">
+                        {conversions.map((conversion, index) => (
+                            <li key={index} title={conversion.reason}>
+                                ⚠️ {describeConversion(conversion)}
+                            </li>
+                        ))}
+                    </ul>
+                )}
+
+                {skippedFiles.length > 0 && (
+                    <p
+                        className="mb-3 text-xs text-amber-600 dark:text-amber-400"
+                        title={skippedFiles.map(getFileName).join('\n')}
+                    >
+                        ⚠️ {skippedFiles.length} 个文件已存在，已跳过
+                    </p>
+                )}
+
                 <div className="max-h-48 overflow-y-auto space-y-1">
                     {files.map((file, index) => (
                         <div
@@ -96,12 +145,26 @@ const ResultList = ({ files }: ResultListProps) => {
                             onClick={() => handlePlayVideo(file)}
                         >
                             <div className="flex items-center gap-2 flex-1 min-w-0">
-                                <svg className="w-4 h-4 flex-shrink-0" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-                                    <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M15 10l4.553-2.276A1 1 0 0121 8.618v6.764a1 1 0 01-1.447.894L15 14M5 18h8a2 2 0 002-2V8a2 2 0 00-2-2H5a2 2 0 00-2 2v8a2 2 0 002 2z" />
-                                </svg>
+                                {thumbnails[index] ? (
+                                    <img
+                                        src={convertFileSrc(thumbnails[index] as string)}
+                                        alt=""
+                                        loading="lazy"
+                                        className="w-16 h-9 flex-shrink-0 rounded object-cover bg-slate-200 dark:bg-slate-700"
+                                    />
+                                ) : (
+                                    <svg className="w-4 h-4 flex-shrink-0" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                        <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M15 10l4.553-2.276A1 1 0 0121 8.618v6.764a1 1 0 01-1.447.894L15 14M5 18h8a2 2 0 002-2V8a2 2 0 00-2-2H5a2 2 0 00-2 2v8a2 2 0 002 2z" />
+                                    </svg>
+                                )}
                                 <span className="truncate" title={file}>
                                     {getFileName(file)}
                                 </span>
+                                {ranges[index] && (
+                                    <span className="flex-shrink-0 text-xs font-mono text-slate-400 dark:text-slate-500">
+                                        {formatSeconds(ranges[index].start_seconds)} - {formatSeconds(ranges[index].end_seconds)}
+                                    </span>
+                                )}
                             </div>
                             <button
                                 className="flex-shrink-0 p-1 rounded hover:bg-slate-200 dark:hover:bg-slate-600 transition-colors"
@@ -1,8 +1,10 @@
 import { useState, useRef, useEffect, useImperativeHandle, forwardRef, useMemo, type ForwardedRef } from 'react';
 import { createPortal } from 'react-dom';
-import { convertFileSrc } from '@tauri-apps/api/core';
+import { convertFileSrc, invoke } from '@tauri-apps/api/core';
+import { open } from '@tauri-apps/plugin-dialog';
 import Hls from 'hls.js';
 import { usePreviewSource } from '../hooks/usePreviewSource';
+import { formatError } from '../utils/appError';
 
 export interface TimeRange {
     id: string;
@@ -41,6 +43,32 @@ const formatDuration = (seconds: number): string => {
     return `${secs}秒`;
 };
 
+interface Filmstrip {
+    sprite_path: string;
+    vtt_path: string;
+    tile_width: number;
+    tile_height: number;
+    columns: number;
+    rows: number;
+    frames: { start_seconds: number; end_seconds: number; time_seconds: number; x: number; y: number }[];
+}
+
+const FILMSTRIP_COUNT = 20;
+
+interface Waveform {
+    duration: number;
+    buckets: number;
+    peaks: number[];
+}
+
+const WAVEFORM_BUCKETS = 800;
+
+interface ExtractedFrame {
+    path: string;
+    width: number;
+    height: number;
+}
+
 interface BatchParseLine {
     lineNumber: number;
     raw: string;
@@ -85,6 +113,45 @@ const TimeRangeEditor = forwardRef(function TimeRangeEditor({
         clamp: true,
         ignoreInvalid: true,
     });
+    const [keyframes, setKeyframes] = useState<number[]>([]);
+    const [filmstrip, setFilmstrip] = useState<Filmstrip | null>(null);
+    const [waveform, setWaveform] = useState<Waveform | null>(null);
+    const [snapshotMessage, setSnapshotMessage] = useState<string | null>(null);
+
+    // Keyframes show where lossless (copy mode) cuts will actually land.
+    useEffect(() => {
+        let cancelled = false;
+        setKeyframes([]);
+        invoke<number[]>('get_keyframes_command', { inputPath: filePath })
+            .then((times) => {
+                if (!cancelled) {
+                    setKeyframes(times);
+                }
+            })
+            .catch(() => {});
+        return () => {
+            cancelled = true;
+        };
+    }, [filePath]);
+
+    useEffect(() => {
+        let cancelled = false;
+        setFilmstrip(null);
+        invoke<Filmstrip>('generate_filmstrip_command', {
+            inputPath: filePath,
+            count: FILMSTRIP_COUNT,
+            width: 160,
+        })
+            .then((strip) => {
+                if (!cancelled) {
+                    setFilmstrip(strip);
+                }
+            })
+            .catch(() => {});
+        return () => {
+            cancelled = true;
+        };
+    }, [filePath]);
 
     const HLS_MIN_SIZE_DEFAULT = 200 * 1024 * 1024;
     const HLS_SEGMENT_SECONDS = 2;
@@ -585,6 +652,22 @@ const TimeRangeEditor = forwardRef(function TimeRangeEditor({
         }
     };
 
+    const handleSnapshot = async () => {
+        const outputDir = await open({ directory: true, multiple: false });
+        if (!outputDir || typeof outputDir !== 'string') return;
+        try {
+            const frame = await invoke<ExtractedFrame>('extract_frame_command', {
+                inputPath: filePath,
+                timeSeconds: currentTime,
+                format: 'png',
+                outputDir,
+            });
+            setSnapshotMessage(`已保存截图 ${frame.width}×${frame.height}: ${frame.path}`);
+        } catch (err) {
+            setSnapshotMessage(`截图失败: ${formatError(err)}`);
+        }
+    };
+
     const handleCancelPending = () => {
         setPendingStart(null);
         setEditingRangeId(null);
@@ -638,6 +721,45 @@ const TimeRangeEditor = forwardRef(function TimeRangeEditor({
         }));
     };
 
+    useEffect(() => {
+        let cancelled = false;
+        const jobId = crypto.randomUUID();
+        setWaveform(null);
+        invoke<Waveform>('get_waveform_command', { inputPath: filePath, buckets: WAVEFORM_BUCKETS, jobId })
+            .then((wave) => {
+                if (!cancelled) {
+                    setWaveform(wave);
+                }
+            })
+            .catch(() => {});
+        return () => {
+            cancelled = true;
+            // Stop decoding a long file once the editor moves on
+            invoke('cancel_split_command', { jobId }).catch(() => {});
+        };
+    }, [filePath]);
+
+    const waveformPath = useMemo(() => {
+        if (!waveform) {
+            return '';
+        }
+        let path = '';
+        for (let i = 0; i < waveform.buckets; i++) {
+            const min = waveform.peaks[i * 2] / 32768;
+            const max = waveform.peaks[i * 2 + 1] / 32768;
+            path += `M${i + 0.5} ${1 - max}V${1 - min}`;
+        }
+        return path;
+    }, [waveform]);
+
+    const MAX_KEYFRAME_TICKS = 2000;
+    const keyframeTicks = useMemo(() => {
+        if (timelineDuration <= 0 || keyframes.length > MAX_KEYFRAME_TICKS) {
+            return [];
+        }
+        return keyframes.map((time) => (time / timelineDuration) * 100);
+    }, [keyframes, timelineDuration]);
+
     const mergeRanges = (items: { startSeconds: number; endSeconds: number }[]) => {
         const sorted = [...items].sort((a, b) => a.startSeconds - b.startSeconds);
         const merged: { startSeconds: number; endSeconds: number }[] = [];
@@ -787,6 +909,33 @@ const TimeRangeEditor = forwardRef(function TimeRangeEditor({
             </div>
 
             <div className="px-4 py-3 space-y-3 bg-slate-50 dark:bg-slate-800/50">
+                {filmstrip && (
+                    <div className="flex w-full overflow-hidden rounded">
+                        {filmstrip.frames.map((frame, index) => (
+                            <div
+                                key={index}
+                                className="flex-1 cursor-pointer"
+                                title={formatTime(frame.time_seconds)}
+                                onClick={() => !isUiDisabled && seekToAbsolute(frame.time_seconds)}
+                                style={{
+                                    aspectRatio: `${filmstrip.tile_width} / ${filmstrip.tile_height}`,
+                                    backgroundImage: `url(${convertFileSrc(filmstrip.sprite_path)})`,
+                                    backgroundSize: `${filmstrip.columns * 100}% ${filmstrip.rows * 100}%`,
+                                    backgroundPosition: `${filmstrip.columns > 1 ? (frame.x / filmstrip.tile_width) / (filmstrip.columns - 1) * 100 : 0}% ${filmstrip.rows > 1 ? (frame.y / filmstrip.tile_height) / (filmstrip.rows - 1) * 100 : 0}%`,
+                                }}
+                            />
+                        ))}
+                    </div>
+                )}
+                {waveform && (
+                    <svg
+                        className="w-full h-10 text-primary-500/60"
+                        viewBox={`0 0 ${waveform.buckets} 2`}
+                        preserveAspectRatio="none"
+                    >
+                        <path d={waveformPath} stroke="currentColor" strokeWidth={1} vectorEffect="non-scaling-stroke" fill="none" />
+                    </svg>
+                )}
                 <div className="relative">
                     <div className="absolute inset-0 flex items-center pointer-events-none" style={{ height: '4px', top: '50%', transform: 'translateY(-50%)' }}>
                         {getTimelineMarkers().map((marker) => (
@@ -796,6 +945,13 @@ const TimeRangeEditor = forwardRef(function TimeRangeEditor({
                                 style={{ left: `${marker.left}%`, width: `${marker.width}%` }}
                             />
                         ))}
+                        {keyframeTicks.map((left, index) => (
+                            <div
+                                key={`kf-${index}`}
+                                className="absolute w-px h-2 bg-amber-500/60"
+                                style={{ left: `${left}%` }}
+                            />
+                        ))}
                     </div>
                     <div className="flex items-center gap-2">
                         <span className="text-xs text-slate-500 dark:text-slate-400 font-mono w-16">
@@ -877,6 +1033,13 @@ const TimeRangeEditor = forwardRef(function TimeRangeEditor({
                                 取消
                             </button>
                         )}
+                        <button
+                            onClick={handleSnapshot}
+                            disabled={isUiDisabled}
+                            className="px-3 py-1.5 text-xs font-medium rounded-lg bg-slate-200 dark:bg-slate-700 text-slate-600 dark:text-slate-300 hover:bg-slate-300 dark:hover:bg-slate-600 transition-colors disabled:opacity-50 flex items-center gap-1"
+                        >
+                            <span>📷</span> 截图
+                        </button>
                         <button
                             onClick={handleSetStart}
                             disabled={isUiDisabled}
@@ -895,6 +1058,12 @@ const TimeRangeEditor = forwardRef(function TimeRangeEditor({
                 </div>
             </div>
 
+            {snapshotMessage && (
+                <div className="px-4 py-2 text-xs text-slate-500 dark:text-slate-400 break-all">
+                    {snapshotMessage}
+                </div>
+            )}
+
             {ranges.length > 0 && (
                 <div className="border-t border-slate-200 dark:border-slate-700">
                     <div className="px-4 py-2 bg-slate-100 dark:bg-slate-800">
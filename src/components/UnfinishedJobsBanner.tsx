@@ -0,0 +1,100 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { ask } from '@tauri-apps/plugin-dialog';
+import { formatError } from '../utils/appError';
+
+interface UnfinishedJob {
+    checkpoint: {
+        job_id: string;
+        input_path: string;
+        output_dir: string;
+        output_files: (string | null)[];
+    };
+    resume_from: number;
+    input_changed: boolean;
+}
+
+const UnfinishedJobsBanner = () => {
+    const [jobs, setJobs] = useState<UnfinishedJob[]>([]);
+    const [error, setError] = useState<string | null>(null);
+
+    // Checkpoints left on disk mean the app quit in the middle of a split
+    useEffect(() => {
+        invoke<UnfinishedJob[]>('list_unfinished_jobs')
+            .then(setJobs)
+            .catch(() => setJobs([]));
+    }, []);
+
+    if (jobs.length === 0) return null;
+
+    const dismiss = (jobId: string) => {
+        setJobs(prev => prev.filter(j => j.checkpoint.job_id !== jobId));
+    };
+
+    const handleResume = async (job: UnfinishedJob) => {
+        const jobId = job.checkpoint.job_id;
+        try {
+            // Earlier segments were cut from the old file, so make the user opt in
+            const confirmInputChanged = job.input_changed
+                ? await ask('源文件在任务中断后已变化，已完成的片段来自旧文件。仍要继续吗？', {
+                    title: '继续未完成的任务',
+                    kind: 'warning',
+                })
+                : false;
+            if (job.input_changed && !confirmInputChanged) return;
+            await invoke('resume_unfinished_job', { jobId, confirmInputChanged });
+            dismiss(jobId);
+        } catch (err) {
+            setError(formatError(err));
+        }
+    };
+
+    const handleDiscard = async (jobId: string) => {
+        try {
+            await invoke('discard_unfinished_job', { jobId });
+            dismiss(jobId);
+        } catch (err) {
+            setError(formatError(err));
+        }
+    };
+
+    const getFileName = (path: string) => path.split('/').pop() || path;
+
+    return (
+        <div className="glass rounded-xl p-4 border border-amber-500/30 space-y-2">
+            <h3 className="text-sm font-medium text-amber-600 dark:text-amber-400">
+                发现未完成的切分任务
+            </h3>
+            {jobs.map(job => (
+                <div key={job.checkpoint.job_id} className="flex items-center justify-between gap-2 text-sm">
+                    <div className="min-w-0">
+                        <p className="truncate text-slate-600 dark:text-slate-300" title={job.checkpoint.input_path}>
+                            {getFileName(job.checkpoint.input_path)}
+                        </p>
+                        <p className="text-xs text-slate-500 dark:text-slate-400">
+                            已完成 {job.resume_from}/{job.checkpoint.output_files.length} 个片段
+                            {job.input_changed && '，⚠️ 源文件已变化'}
+                        </p>
+                    </div>
+                    <div className="flex items-center gap-2 flex-shrink-0">
+                        <button
+                            onClick={() => handleResume(job)}
+                            className="px-3 py-1 text-xs font-medium rounded-lg bg-primary-600 text-white hover:bg-primary-500 transition-colors"
+                        >
+                            继续
+                        </button>
+                        <button
+                            onClick={() => handleDiscard(job.checkpoint.job_id)}
+                            className="px-3 py-1 text-xs font-medium rounded-lg bg-slate-200 dark:bg-slate-700 text-slate-600 dark:text-slate-300 hover:bg-slate-300 dark:hover:bg-slate-600 transition-colors"
+                        >
+                            放弃
+                        </button>
+                    </div>
+                </div>
+            ))}
+            {error && <p className="text-xs text-red-500">{error}</p>}
+        </div>
+    );
+};
+
+export default UnfinishedJobsBanner;
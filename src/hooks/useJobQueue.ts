@@ -0,0 +1,103 @@
+import { useState, useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import type { Container, CutMode, EncodeOptions, OverwritePolicy, ThumbnailMode } from './useVideoSplit';
+import type { AppError } from '../utils/appError';
+
+export type SplitSpec =
+    | { kind: 'duration'; segment_duration: number; container?: Container | null; name_template?: string | null; overwrite?: OverwritePolicy }
+    | { kind: 'count'; part_count: number; container?: Container | null; name_template?: string | null; overwrite?: OverwritePolicy }
+    | { kind: 'size'; max_bytes: number; container?: Container | null; name_template?: string | null; overwrite?: OverwritePolicy }
+    | { kind: 'ranges'; ranges: { start_seconds: number; end_seconds: number; label?: string | null }[]; mode: CutMode; max_workers?: number | null; encode?: EncodeOptions; container?: Container | null; name_template?: string | null; overwrite?: OverwritePolicy }
+    | { kind: 'chapters'; mode: CutMode; encode?: EncodeOptions; container?: Container | null; name_template?: string | null; overwrite?: OverwritePolicy };
+
+export type JobStatus = 'queued' | 'running' | 'completed' | 'failed' | 'cancelled';
+
+export interface QueuedJob {
+    id: string;
+    input_path: string;
+    output_dir: string;
+    spec: SplitSpec;
+    thumbnails: ThumbnailMode | null;
+    status: JobStatus;
+    result: { output_files: string[] } | null;
+    error: AppError | null;
+}
+
+export interface HistoryEntry {
+    id: string;
+    created_at: number;
+    input_path: string;
+    fingerprint: string | null;
+    output_dir: string;
+    spec: SplitSpec;
+    ranges: { start_seconds: number; end_seconds: number; label?: string | null }[];
+    output_files: string[];
+    duration_seconds: number;
+    status: JobStatus;
+    error: AppError | null;
+}
+
+export function useJobQueue() {
+    const [jobs, setJobs] = useState<QueuedJob[]>([]);
+
+    useEffect(() => {
+        invoke<QueuedJob[]>('list_jobs').then(setJobs).catch(() => { });
+
+        const unlisten = listen<QueuedJob>('queue-job-updated', (event) => {
+            const job = event.payload;
+            setJobs(prev => {
+                const index = prev.findIndex(j => j.id === job.id);
+                if (index === -1) return [...prev, job];
+                const next = [...prev];
+                next[index] = job;
+                return next;
+            });
+        });
+
+        return () => {
+            unlisten.then(fn => fn());
+        };
+    }, []);
+
+    const enqueueJob = useCallback(async (
+        inputPath: string,
+        outputDir: string,
+        spec: SplitSpec,
+        thumbnails: ThumbnailMode | null = null
+    ) => {
+        return invoke<QueuedJob>('enqueue_job', { inputPath, outputDir, spec, thumbnails });
+    }, []);
+
+    const removeJob = useCallback(async (jobId: string) => {
+        await invoke<boolean>('remove_job', { jobId });
+        setJobs(prev => prev.filter(j => j.id !== jobId));
+    }, []);
+
+    const reorderJobs = useCallback(async (jobIds: string[]) => {
+        const next = await invoke<QueuedJob[]>('reorder_jobs', { jobIds });
+        setJobs(next);
+    }, []);
+
+    const listHistory = useCallback(async () => {
+        return invoke<HistoryEntry[]>('list_history');
+    }, []);
+
+    // Re-queue a past split, optionally against a new input (e.g. a re-exported master)
+    const rerunHistoryEntry = useCallback(async (
+        entryId: string,
+        inputPath: string | null = null,
+        outputDir: string | null = null
+    ) => {
+        return invoke<QueuedJob>('rerun_history_entry', { entryId, inputPath, outputDir });
+    }, []);
+
+    return {
+        jobs,
+        enqueueJob,
+        removeJob,
+        reorderJobs,
+        listHistory,
+        rerunHistoryEntry,
+    };
+}
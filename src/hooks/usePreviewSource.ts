@@ -1,5 +1,6 @@
 import { useEffect, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { formatError } from '../utils/appError';
 
 export type PreviewSourceKind = 'file' | 'hls';
 
@@ -60,7 +61,7 @@ export function usePreviewSource(filePath: string, options: PreviewSourceOptions
             })
             .catch((err) => {
                 if (!cancelled) {
-                    setPrepareError(String(err));
+                    setPrepareError(formatError(err));
                 }
             })
             .finally(() => {
@@ -1,6 +1,7 @@
-import { useState, useEffect, useCallback } from 'react';
+import { useState, useEffect, useCallback, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
+import { formatError, isCancelled } from '../utils/appError';
 
 interface VideoInfo {
     path: string;
@@ -8,21 +9,132 @@ interface VideoInfo {
     duration_formatted: string;
     filename: string;
     file_size: number;
+    chapters: Chapter[];
+    format_name: string | null;
+    bit_rate: number | null;
+    video: VideoStreamInfo | null;
+    audio_tracks: AudioTrackInfo[];
+    subtitle_tracks: SubtitleTrackInfo[];
+    tags: Record<string, string>;
+}
+
+interface VideoStreamInfo {
+    codec: string;
+    profile: string | null;
+    width: number;
+    height: number;
+    frame_rate: number | null;
+    avg_frame_rate: number | null;
+    is_vfr: boolean;
+    rotation: number;
+    pix_fmt: string | null;
+    bit_rate: number | null;
+    level: number | null;
+    frame_rate_ratio: string | null;
+    timescale: number | null;
+    color_space: string | null;
+    color_primaries: string | null;
+    color_transfer: string | null;
+}
+
+interface AudioTrackInfo {
+    index: number;
+    codec: string;
+    language: string | null;
+    title: string | null;
+    channels: number;
+    channel_layout: string | null;
+    sample_rate: number | null;
+    bit_rate: number | null;
+}
+
+interface SubtitleTrackInfo {
+    index: number;
+    codec: string;
+    language: string | null;
+    title: string | null;
+}
+
+interface Chapter {
+    index: number;
+    title: string | null;
+    start_seconds: number;
+    end_seconds: number;
 }
 
 interface SplitProgress {
+    job_id: string;
     current_segment: number;
     total_segments: number;
     percentage: number;
     current_file: string;
+    output_file: string | null;
+    speed: number | null;
+    eta_seconds: number | null;
+}
+
+export interface StreamConversion {
+    kind: 'video' | 'audio' | 'subtitle';
+    stream_index: number | null;
+    codec: string;
+    target: string | null;
+    reason: string;
 }
 
 interface SplitResult {
     success: boolean;
     output_files: string[];
+    output_ranges: DetectedRange[];
+    thumbnails: (string | null)[];
+    conversions: StreamConversion[];
+    skipped_files: string[];
     error: string | null;
 }
 
+export type CutMode = 'copy' | 'reencode' | 'smart';
+
+export type ThumbnailMode = 'midpoint' | 'interesting';
+
+export type Container = 'mp4' | 'mkv' | 'mov' | 'webm' | 'ts';
+
+export type OverwritePolicy = 'overwrite' | 'skip' | 'rename' | 'fail';
+
+export type VideoCodec = 'x264' | 'x265' | 'vp9' | 'av1' | 'prores';
+
+export type AudioCodec = 'aac' | 'opus' | 'mp3' | 'flac' | 'pcm' | 'copy';
+
+export interface EncodeOptions {
+    video_codec: VideoCodec;
+    crf?: number | null;
+    video_bitrate?: string | null;
+    preset?: string | null;
+    pix_fmt?: string | null;
+    max_height?: number | null;
+    audio_codec: AudioCodec;
+    audio_bitrate?: string | null;
+}
+
+export interface EncodePreset {
+    name: string;
+    encode: EncodeOptions;
+    built_in: boolean;
+}
+
+export const DEFAULT_ENCODE_OPTIONS: EncodeOptions = {
+    video_codec: 'x264',
+    audio_codec: 'aac',
+};
+
+interface SplitCancelled {
+    job_id: string;
+    removed_files: string[];
+}
+
+interface DetectedRange {
+    start_seconds: number;
+    end_seconds: number;
+}
+
 interface TimeRange {
     id: string;
     startTime: number;
@@ -36,18 +148,35 @@ export function useVideoSplit() {
     const [progress, setProgress] = useState<SplitProgress | null>(null);
     const [result, setResult] = useState<SplitResult | null>(null);
     const [error, setError] = useState<string | null>(null);
+    const jobIdRef = useRef<string | null>(null);
+    const cancelledRef = useRef(false);
 
     // Listen for progress events
     useEffect(() => {
         const unlisten = listen<SplitProgress>('split-progress', (event) => {
-            setProgress(event.payload);
+            if (event.payload.job_id === jobIdRef.current) {
+                setProgress(event.payload);
+            }
+        });
+        const unlistenCancelled = listen<SplitCancelled>('split-cancelled', (event) => {
+            if (event.payload.job_id === jobIdRef.current) {
+                setProgress(null);
+            }
         });
 
         return () => {
             unlisten.then((fn) => fn());
+            unlistenCancelled.then((fn) => fn());
         };
     }, []);
 
+    const startJob = () => {
+        const jobId = crypto.randomUUID();
+        jobIdRef.current = jobId;
+        cancelledRef.current = false;
+        return jobId;
+    };
+
     const loadVideoInfo = useCallback(async (path: string) => {
         setIsLoading(true);
         setError(null);
@@ -58,7 +187,7 @@ export function useVideoSplit() {
             const info = await invoke<VideoInfo>('get_video_info', { path });
             setVideoInfo(info);
         } catch (err) {
-            setError(err as string);
+            setError(formatError(err));
             setVideoInfo(null);
         } finally {
             setIsLoading(false);
@@ -68,16 +197,25 @@ export function useVideoSplit() {
     const splitVideo = useCallback(async (
         inputPath: string,
         outputDir: string,
-        segmentDuration: number
+        segmentDuration: number,
+        thumbnails: ThumbnailMode | null = null,
+        container: Container | null = null,
+        nameTemplate: string | null = null,
+        overwrite: OverwritePolicy = 'overwrite'
     ) => {
+        const jobId = startJob();
         setIsProcessing(true);
         setError(null);
         setResult(null);
         setProgress({
+            job_id: jobId,
             current_segment: 0,
             total_segments: Math.ceil(videoInfo?.duration || 0 / segmentDuration),
             percentage: 0,
             current_file: '准备中...',
+            output_file: null,
+            speed: null,
+            eta_seconds: null,
         });
 
         try {
@@ -85,10 +223,17 @@ export function useVideoSplit() {
                 inputPath,
                 outputDir,
                 segmentDuration,
+                thumbnails,
+                container,
+                nameTemplate,
+                overwrite,
+                jobId,
             });
             setResult(splitResult);
         } catch (err) {
-            setError(err as string);
+            if (!cancelledRef.current && !isCancelled(err)) {
+                setError(formatError(err));
+            }
         } finally {
             setIsProcessing(false);
         }
@@ -97,16 +242,27 @@ export function useVideoSplit() {
     const splitVideoByRanges = useCallback(async (
         inputPath: string,
         outputDir: string,
-        ranges: TimeRange[]
+        ranges: TimeRange[],
+        mode: CutMode = 'reencode',
+        thumbnails: ThumbnailMode | null = null,
+        encode: EncodeOptions = DEFAULT_ENCODE_OPTIONS,
+        container: Container | null = null,
+        nameTemplate: string | null = null,
+        overwrite: OverwritePolicy = 'overwrite'
     ) => {
+        const jobId = startJob();
         setIsProcessing(true);
         setError(null);
         setResult(null);
         setProgress({
+            job_id: jobId,
             current_segment: 0,
             total_segments: ranges.length,
             percentage: 0,
             current_file: '准备中...',
+            output_file: null,
+            speed: null,
+            eta_seconds: null,
         });
 
         try {
@@ -118,15 +274,223 @@ export function useVideoSplit() {
                 inputPath,
                 outputDir,
                 ranges: rangesPayload,
+                mode,
+                thumbnails,
+                encode,
+                container,
+                nameTemplate,
+                overwrite,
+                jobId,
+            });
+            setResult(splitResult);
+        } catch (err) {
+            if (!cancelledRef.current && !isCancelled(err)) {
+                setError(formatError(err));
+            }
+        } finally {
+            setIsProcessing(false);
+        }
+    }, []);
+
+    const splitVideoByCount = useCallback(async (
+        inputPath: string,
+        outputDir: string,
+        partCount: number
+    ) => {
+        const jobId = startJob();
+        setIsProcessing(true);
+        setError(null);
+        setResult(null);
+        setProgress({
+            job_id: jobId,
+            current_segment: 0,
+            total_segments: partCount,
+            percentage: 0,
+            current_file: '准备中...',
+            output_file: null,
+            speed: null,
+            eta_seconds: null,
+        });
+
+        try {
+            const splitResult = await invoke<SplitResult>('split_video_by_count_command', {
+                inputPath,
+                outputDir,
+                partCount,
+                jobId,
             });
             setResult(splitResult);
         } catch (err) {
-            setError(err as string);
+            if (!cancelledRef.current && !isCancelled(err)) {
+                setError(formatError(err));
+            }
         } finally {
             setIsProcessing(false);
         }
     }, []);
 
+    const splitVideoByChapters = useCallback(async (
+        inputPath: string,
+        outputDir: string,
+        mode: CutMode = 'reencode',
+        encode: EncodeOptions = DEFAULT_ENCODE_OPTIONS,
+        container: Container | null = null,
+        nameTemplate: string | null = null,
+        overwrite: OverwritePolicy = 'overwrite'
+    ) => {
+        const jobId = startJob();
+        setIsProcessing(true);
+        setError(null);
+        setResult(null);
+        setProgress({
+            job_id: jobId,
+            current_segment: 0,
+            total_segments: videoInfo?.chapters.length || 0,
+            percentage: 0,
+            current_file: '准备中...',
+            output_file: null,
+            speed: null,
+            eta_seconds: null,
+        });
+
+        try {
+            const splitResult = await invoke<SplitResult>('split_video_by_chapters_command', {
+                inputPath,
+                outputDir,
+                mode,
+                encode,
+                container,
+                nameTemplate,
+                overwrite,
+                jobId,
+            });
+            setResult(splitResult);
+        } catch (err) {
+            if (!cancelledRef.current && !isCancelled(err)) {
+                setError(formatError(err));
+            }
+        } finally {
+            setIsProcessing(false);
+        }
+    }, [videoInfo]);
+
+    const splitVideoBySize = useCallback(async (
+        inputPath: string,
+        outputDir: string,
+        maxBytes: number
+    ) => {
+        const jobId = startJob();
+        setIsProcessing(true);
+        setError(null);
+        setResult(null);
+        setProgress({
+            job_id: jobId,
+            current_segment: 0,
+            total_segments: Math.ceil((videoInfo?.file_size || 0) / maxBytes),
+            percentage: 0,
+            current_file: '准备中...',
+            output_file: null,
+            speed: null,
+            eta_seconds: null,
+        });
+
+        try {
+            const splitResult = await invoke<SplitResult>('split_video_by_size_command', {
+                inputPath,
+                outputDir,
+                maxBytes,
+                jobId,
+            });
+            setResult(splitResult);
+        } catch (err) {
+            if (!cancelledRef.current && !isCancelled(err)) {
+                setError(formatError(err));
+            }
+        } finally {
+            setIsProcessing(false);
+        }
+    }, [videoInfo]);
+
+    const detectScenes = useCallback(async (
+        inputPath: string,
+        threshold: number,
+        minSegmentSeconds: number
+    ): Promise<TimeRange[] | null> => {
+        const jobId = startJob();
+        setIsProcessing(true);
+        setError(null);
+        setResult(null);
+        setProgress(null);
+
+        try {
+            const detected = await invoke<DetectedRange[]>('detect_scenes_command', {
+                inputPath,
+                threshold,
+                minSegmentSeconds,
+                jobId,
+            });
+            return detected.map(r => ({
+                id: crypto.randomUUID(),
+                startTime: r.start_seconds,
+                endTime: r.end_seconds,
+            }));
+        } catch (err) {
+            if (!cancelledRef.current && !isCancelled(err)) {
+                setError(formatError(err));
+            }
+            return null;
+        } finally {
+            setIsProcessing(false);
+            setProgress(null);
+        }
+    }, []);
+
+    const detectSilence = useCallback(async (
+        inputPath: string,
+        noiseDb: number,
+        minSilenceSeconds: number
+    ): Promise<TimeRange[] | null> => {
+        const jobId = startJob();
+        setIsProcessing(true);
+        setError(null);
+        setResult(null);
+        setProgress(null);
+
+        try {
+            const detected = await invoke<DetectedRange[]>('detect_silence_command', {
+                inputPath,
+                noiseDb,
+                minSilenceSeconds,
+                jobId,
+            });
+            return detected.map(r => ({
+                id: crypto.randomUUID(),
+                startTime: r.start_seconds,
+                endTime: r.end_seconds,
+            }));
+        } catch (err) {
+            if (!cancelledRef.current && !isCancelled(err)) {
+                setError(formatError(err));
+            }
+            return null;
+        } finally {
+            setIsProcessing(false);
+            setProgress(null);
+        }
+    }, []);
+
+    const cancelSplit = useCallback(async () => {
+        const jobId = jobIdRef.current;
+        if (!jobId) return;
+
+        cancelledRef.current = true;
+        try {
+            await invoke<boolean>('cancel_split_command', { jobId });
+        } catch (err) {
+            setError(formatError(err));
+        }
+    }, []);
+
     const reset = useCallback(() => {
         setVideoInfo(null);
         setProgress(null);
@@ -144,6 +508,12 @@ export function useVideoSplit() {
         loadVideoInfo,
         splitVideo,
         splitVideoByRanges,
+        splitVideoByCount,
+        splitVideoByChapters,
+        splitVideoBySize,
+        detectScenes,
+        detectSilence,
+        cancelSplit,
         reset,
     };
 }
@@ -0,0 +1,52 @@
+export type ErrorCode =
+    | 'ffmpeg_not_found'
+    | 'probe_failed'
+    | 'ffmpeg_failed'
+    | 'unsupported_codec'
+    | 'disk_full'
+    | 'permission_denied'
+    | 'timeout'
+    | 'cancelled'
+    | 'invalid_input'
+    | 'not_found'
+    | 'output_exists'
+    | 'io'
+    | 'internal';
+
+/** Error object rejected by every backend command */
+export interface AppError {
+    code: ErrorCode;
+    message_key: string;
+    message: string;
+    details: string | null;
+    stderr_tail: string | null;
+}
+
+const MESSAGES: Record<string, string> = {
+    'errors.ffmpeg_not_found': '内置 FFmpeg 未找到，请重新安装应用',
+    'errors.probe_failed': '无法读取视频信息',
+    'errors.ffmpeg_failed': 'FFmpeg 处理失败',
+    'errors.unsupported_codec': '不支持的编码格式',
+    'errors.disk_full': '磁盘空间不足',
+    'errors.permission_denied': '没有权限访问该文件或目录',
+    'errors.timeout': '操作超时，请重试',
+    'errors.cancelled': '切分已取消',
+    'errors.invalid_input': '参数无效',
+    'errors.not_found': '未找到',
+    'errors.output_exists': '输出文件已存在',
+    'errors.io': '文件读写失败',
+    'errors.internal': '内部错误',
+};
+
+export const isAppError = (err: unknown): err is AppError =>
+    typeof err === 'object' && err !== null && 'code' in err && 'message_key' in err;
+
+export const isCancelled = (err: unknown): boolean =>
+    isAppError(err) && err.code === 'cancelled';
+
+/** Localized one-line message for anything a command rejected with */
+export const formatError = (err: unknown): string => {
+    if (!isAppError(err)) return String(err);
+    const text = MESSAGES[err.message_key] ?? err.message;
+    return err.details ? `${text}：${err.details}` : text;
+};